@@ -0,0 +1,61 @@
+extern crate nalgebra as na;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use modern_robotics::dynamics::{DynamicChain, SpatialInertia};
+use modern_robotics::groups::{So3ToSe3, ToSo3, Wrench};
+use na::{DVector, Dyn, Matrix3, OMatrix, Vector3, Vector6, U6};
+use std::hint::black_box;
+
+// Chain of n links alternating joint axes, with offset centres of mass and a prismatic joint
+fn chain(n: usize) -> DynamicChain<f64, Dyn> {
+    let mut frames = Vec::new();
+    let mut screws = Vec::new();
+    let mut inertias = Vec::new();
+    for i in 0..n {
+        let p = Vector3::new(0.3 * i as f64, 0.05 * (i % 3) as f64, 0.1);
+        let w = [Vector3::z(), Vector3::y(), Vector3::x()][i % 3];
+        frames.push(Matrix3::identity().to_so3().to_se3(p));
+        screws.push(if i == 2 {
+            Vector6::new(0.0, 0.0, 0.0, w[0], w[1], w[2])
+        } else {
+            let v = -w.cross(&p);
+            Vector6::new(w[0], w[1], w[2], v[0], v[1], v[2])
+        });
+        let inertia = Matrix3::from_diagonal(&Vector3::new(0.02, 0.03, 0.04));
+        inertias.push(SpatialInertia::from_mass_com(
+            1.0 + 0.1 * i as f64,
+            &Vector3::new(0.15, 0.01, -0.02),
+            &inertia,
+        ));
+    }
+    let tip = Matrix3::identity()
+        .to_so3()
+        .to_se3(Vector3::new(0.3 * n as f64, 0.0, 0.1));
+    DynamicChain::new(
+        OMatrix::<f64, U6, Dyn>::from_columns(&screws),
+        DVector::from_vec(frames),
+        tip,
+        DVector::from_vec(inertias),
+    )
+}
+
+fn forward_dynamics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("forward_dynamics");
+    let g = Vector3::new(0.0, 0.0, -9.8);
+    let f_tip = Wrench(Vector6::zeros());
+    for n in [6, 12, 24, 48] {
+        let robot = chain(n);
+        let theta = DVector::from_fn(n, |i, _| 0.1 * i as f64);
+        let dtheta = DVector::from_element(n, 0.2);
+        let tau = DVector::from_element(n, 0.5);
+        group.bench_with_input(BenchmarkId::new("mass_matrix", n), &n, |b, _| {
+            b.iter(|| robot.forward_dynamics(black_box(&theta), &dtheta, &tau, &g, &f_tip))
+        });
+        group.bench_with_input(BenchmarkId::new("articulated_body", n), &n, |b, _| {
+            b.iter(|| robot.forward_dynamics_aba(black_box(&theta), &dtheta, &tau, &g, &f_tip))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, forward_dynamics);
+criterion_main!(benches);
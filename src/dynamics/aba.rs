@@ -0,0 +1,170 @@
+extern crate nalgebra as na;
+use crate::dynamics::{DynamicChain, SpatialInertia};
+use crate::groups::{Ad, Adjoint, Inverse, MatrixExp, Se3Matrix, ToTwist, Wrench};
+use crate::interfaces::Numeric;
+use na::allocator::Allocator;
+use na::{DefaultAllocator, Dim, Matrix4, OMatrix, OVector, RealField, Vector3, Vector6, U1, U6};
+
+impl<T: Numeric<T> + RealField, N: Dim> DynamicChain<T, N>
+where
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N>
+        + Allocator<T, N>
+        + Allocator<Se3Matrix<T>, N>
+        + Allocator<SpatialInertia<T>, N>,
+{
+    // Featherstone's articulated-body algorithm, O(n) and without forming the mass matrix,
+    // None when a link's articulated inertia about its joint axis vanishes
+    pub fn forward_dynamics_aba(
+        &self,
+        theta: &OVector<T, N>,
+        dtheta: &OVector<T, N>,
+        tau: &OVector<T, N>,
+        g: &Vector3<T>,
+        f_tip: &Wrench<T>,
+    ) -> Option<OVector<T, N>> {
+        let n = self.dof();
+        let (ncols, _) = theta.shape_generic();
+        let a = self.link_screws();
+        // T_i(i-1), pose of {i-1} seen from {i}
+        let mut frames = OVector::from_element_generic(ncols, U1, Se3Matrix(Matrix4::identity()));
+        // Velocity-product accelerations ad_Vi A_i dtheta_i
+        let mut bias_accels = OMatrix::zeros_generic(U6, ncols);
+        let mut inertias = self.inertias.clone();
+        let mut bias_forces = OMatrix::zeros_generic(U6, ncols);
+
+        let mut v = Vector6::zeros();
+        for i in 0..n {
+            let ai: Vector6<T> = a.column(i).into_owned();
+            frames[i] = (ai * -theta[i]).to_twist().exp() * self.relative_frame(i).inv();
+            v = frames[i].adjoint() * v + ai * dtheta[i];
+            let twist = v.to_twist();
+            bias_accels.set_column(i, &(twist.ad() * ai * dtheta[i]));
+            let gi = self.inertias[i];
+            bias_forces.set_column(i, &(-twist.ad().transpose() * (gi.0 * v)));
+        }
+        if n > 0 {
+            let tip = self.relative_frame(n).inv();
+            let f = bias_forces.column(n - 1) + tip.coadjoint() * f_tip.0;
+            bias_forces.set_column(n - 1, &f);
+        }
+
+        let mut u = OMatrix::zeros_generic(U6, ncols);
+        let mut d = OVector::zeros_generic(ncols, U1);
+        let mut torques = OVector::zeros_generic(ncols, U1);
+        for i in (0..n).rev() {
+            let ai: Vector6<T> = a.column(i).into_owned();
+            let ia = inertias[i].0;
+            let ui = ia * ai;
+            d[i] = ai.dot(&ui);
+            // Relative to the size of the articulated inertia, so tiny robots are not rejected
+            let scale = ia.abs().max() * ai.norm_squared();
+            if !d[i].is_finite() || d[i] <= scale * na::convert(1e-12) {
+                return None;
+            }
+            torques[i] = tau[i] - ai.dot(&bias_forces.column(i));
+            u.set_column(i, &ui);
+            if i > 0 {
+                let ia_child = ia - ui * ui.transpose() / d[i];
+                let pa_child = bias_forces.column(i)
+                    + ia_child * bias_accels.column(i)
+                    + ui * (torques[i] / d[i]);
+                let coadjoint = frames[i].coadjoint();
+                let adjoint = coadjoint.transpose();
+                inertias[i - 1] =
+                    SpatialInertia(inertias[i - 1].0 + coadjoint * ia_child * adjoint);
+                let p = bias_forces.column(i - 1) + coadjoint * pa_child;
+                bias_forces.set_column(i - 1, &p);
+            }
+        }
+
+        let mut ddtheta = OVector::zeros_generic(ncols, U1);
+        let mut vdot = Vector6::new(T::zero(), T::zero(), T::zero(), -g[0], -g[1], -g[2]);
+        for i in 0..n {
+            let ai: Vector6<T> = a.column(i).into_owned();
+            let vdot_bias = frames[i].adjoint() * vdot + bias_accels.column(i);
+            ddtheta[i] = (torques[i] - u.column(i).dot(&vdot_bias)) / d[i];
+            vdot = vdot_bias + ai * ddtheta[i];
+        }
+        Some(ddtheta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::fixtures::{long_chain, textbook_chain};
+    use crate::helpers::near_zero;
+    use na::DVector;
+
+    #[test]
+    fn aba_textbook() {
+        let ddtheta = textbook_chain()
+            .forward_dynamics_aba(
+                &Vector3::new(0.1, 0.1, 0.1),
+                &Vector3::new(0.1, 0.2, 0.3),
+                &Vector3::new(0.5, 0.6, 0.7),
+                &Vector3::new(0.0, 0.0, -9.8),
+                &Wrench(Vector6::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0)),
+            )
+            .unwrap();
+        let expected = Vector3::new(-0.97392907, 25.58466784, -32.91499212);
+        assert!(near_zero((ddtheta - expected).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn aba_matches_mass_matrix_long_chain() {
+        let n = 12;
+        let chain = long_chain(n);
+        let theta = DVector::from_fn(n, |i, _| 0.3 * (i as f64).sin());
+        let dtheta = DVector::from_fn(n, |i, _| 0.5 - 0.1 * i as f64);
+        let tau = DVector::from_fn(n, |i, _| (i as f64 * 0.7).cos());
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let f_tip = Wrench(Vector6::new(0.1, -0.3, 0.2, 1.0, -2.0, 0.5));
+        let aba = chain
+            .forward_dynamics_aba(&theta, &dtheta, &tau, &g, &f_tip)
+            .unwrap();
+        let crba = chain
+            .forward_dynamics(&theta, &dtheta, &tau, &g, &f_tip)
+            .unwrap();
+        assert!(near_zero((&aba - &crba).norm() / crba.norm(), Some(1e-10)));
+        // And the inverse dynamics recovers the applied torques
+        let tau_back = chain.inverse_dynamics(&theta, &dtheta, &aba, &g, &f_tip);
+        assert!(near_zero((tau_back - tau).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn aba_massless_tip_link() {
+        let mut chain = textbook_chain();
+        chain.inertias[2] = SpatialInertia::zeros();
+        let zero = Vector3::zeros();
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let f_tip = Wrench(Vector6::zeros());
+        assert_eq!(
+            chain.forward_dynamics_aba(&zero, &zero, &zero, &g, &f_tip),
+            None
+        );
+    }
+
+    #[test]
+    fn aba_tiny_inertias() {
+        // Scaling every inertia, torque and tip wrench by k leaves the accelerations unchanged
+        let k = 1e-14;
+        let chain = textbook_chain();
+        let mut tiny = chain.clone();
+        for i in 0..3 {
+            tiny.inertias[i] = SpatialInertia(chain.inertias[i].0 * k);
+        }
+        let (theta, dtheta) = (Vector3::new(0.1, 0.1, 0.1), Vector3::new(0.1, 0.2, 0.3));
+        let tau = Vector3::new(0.5, 0.6, 0.7);
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let f_tip = Vector6::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0);
+        let expected = chain
+            .forward_dynamics_aba(&theta, &dtheta, &tau, &g, &Wrench(f_tip))
+            .unwrap();
+        let scaled = tiny
+            .forward_dynamics_aba(&theta, &dtheta, &(tau * k), &g, &Wrench(f_tip * k))
+            .unwrap();
+        assert!(near_zero((scaled - expected).norm(), Some(1e-6)));
+    }
+}
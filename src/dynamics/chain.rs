@@ -0,0 +1,132 @@
+extern crate nalgebra as na;
+use crate::dynamics::SpatialInertia;
+use crate::groups::{Adjoint, Inverse, Se3Matrix};
+use crate::interfaces::Numeric;
+use crate::kinematics::{ScrewFrame, SerialChain};
+use na::allocator::Allocator;
+use na::{DefaultAllocator, Dim, Matrix4, OMatrix, OVector, RealField, U1, U6};
+
+// Serial chain with a frame and spatial inertia per link, as in chapter 8
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynamicChain<T: Numeric<T>, N: Dim>
+where
+    DefaultAllocator:
+        Allocator<T, U6, N> + Allocator<Se3Matrix<T>, N> + Allocator<SpatialInertia<T>, N>,
+{
+    // Space frame screw axes
+    pub screws: OMatrix<T, U6, N>,
+    // M_0i, pose of link frame {i} at zero joint values
+    pub link_frames: OVector<Se3Matrix<T>, N>,
+    // M_0(n+1), pose of the end-effector frame at zero joint values
+    pub tip: Se3Matrix<T>,
+    // G_i, spatial inertia of link i expressed in {i}
+    pub inertias: OVector<SpatialInertia<T>, N>,
+}
+
+impl<T: Numeric<T> + RealField, N: Dim> DynamicChain<T, N>
+where
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N>
+        + Allocator<T, N>
+        + Allocator<Se3Matrix<T>, N>
+        + Allocator<SpatialInertia<T>, N>,
+{
+    pub fn new(
+        screws: OMatrix<T, U6, N>,
+        link_frames: OVector<Se3Matrix<T>, N>,
+        tip: Se3Matrix<T>,
+        inertias: OVector<SpatialInertia<T>, N>,
+    ) -> Self {
+        DynamicChain {
+            screws,
+            link_frames,
+            tip,
+            inertias,
+        }
+    }
+
+    // Textbook form, mlist holds the n+1 relative frames M_(i-1)i ending with the end-effector
+    pub fn from_mlist(
+        mlist: &[Se3Matrix<T>],
+        glist: &[SpatialInertia<T>],
+        slist: OMatrix<T, U6, N>,
+    ) -> Self {
+        let (_, ncols) = slist.shape_generic();
+        let n = ncols.value();
+        assert_eq!(
+            mlist.len(),
+            n + 1,
+            "DynamicChain::from_mlist: expected {} frames in mlist for {} joints, got {}",
+            n + 1,
+            n,
+            mlist.len()
+        );
+        assert_eq!(
+            glist.len(),
+            n,
+            "DynamicChain::from_mlist: expected {} inertias in glist for {} joints, got {}",
+            n,
+            n,
+            glist.len()
+        );
+        let identity = Se3Matrix(Matrix4::identity());
+        let mut link_frames = OVector::from_element_generic(ncols, U1, identity);
+        let mut frame = identity;
+        for i in 0..link_frames.len() {
+            frame = frame * mlist[i];
+            link_frames[i] = frame;
+        }
+        let tip = frame * mlist[link_frames.len()];
+        let inertias = OVector::from_fn_generic(ncols, U1, |i, _| glist[i]);
+        DynamicChain::new(slist, link_frames, tip, inertias)
+    }
+
+    pub fn dof(&self) -> usize {
+        self.screws.ncols()
+    }
+
+    // M_(i-1)i, for i = dof this is the end-effector frame relative to the last link
+    pub fn relative_frame(&self, i: usize) -> Se3Matrix<T> {
+        let to = if i == self.dof() {
+            self.tip
+        } else {
+            self.link_frames[i]
+        };
+        match i {
+            0 => to,
+            _ => self.link_frames[i - 1].inv() * to,
+        }
+    }
+
+    // A_i, screw axis of joint i expressed in link frame {i}
+    pub fn link_screws(&self) -> OMatrix<T, U6, N> {
+        let mut a = self.screws.clone_owned();
+        for i in 0..self.dof() {
+            let s = self.screws.column(i).into_owned();
+            a.set_column(i, &(self.link_frames[i].inv().adjoint() * s));
+        }
+        a
+    }
+
+    pub fn to_serial_chain(&self) -> SerialChain<T, N> {
+        SerialChain::new(self.tip, self.screws.clone_owned(), ScrewFrame::Space)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::fixtures::{textbook_glist, textbook_mlist, textbook_slist};
+
+    #[test]
+    #[should_panic(expected = "expected 4 frames in mlist for 3 joints, got 3")]
+    fn from_mlist_missing_tip_frame() {
+        DynamicChain::from_mlist(&textbook_mlist()[..3], &textbook_glist(), textbook_slist());
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 3 inertias in glist for 3 joints, got 2")]
+    fn from_mlist_missing_inertia() {
+        DynamicChain::from_mlist(&textbook_mlist(), &textbook_glist()[..2], textbook_slist());
+    }
+}
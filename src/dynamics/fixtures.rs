@@ -0,0 +1,82 @@
+// Chains shared by the dynamics tests
+extern crate nalgebra as na;
+use crate::dynamics::{DynamicChain, SpatialInertia};
+use crate::groups::{Se3Matrix, So3ToSe3, ToSe3, ToSo3};
+use na::{DVector, Dyn, Matrix3, Matrix4, Matrix6, Matrix6x3, OMatrix, Vector3, Vector6, U3, U6};
+
+// UR5 style three link arm from the chapter 8 exercises
+pub(crate) fn textbook_mlist() -> Vec<Se3Matrix<f64>> {
+    vec![
+        Matrix4::new(
+            1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.089159, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3(),
+        Matrix4::new(
+            0.0, 0.0, 1.0, 0.28, 0.0, 1.0, 0.0, 0.13585, -1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3(),
+        Matrix4::new(
+            1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, -0.1197, 0.0, 0.0, 1.0, 0.395, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3(),
+        Matrix4::new(
+            1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.14225, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3(),
+    ]
+}
+
+pub(crate) fn textbook_glist() -> Vec<SpatialInertia<f64>> {
+    [
+        Vector6::new(0.010267, 0.010267, 0.00666, 3.7, 3.7, 3.7),
+        Vector6::new(0.22689, 0.22689, 0.0151074, 8.393, 8.393, 8.393),
+        Vector6::new(0.0494433, 0.0494433, 0.004095, 2.275, 2.275, 2.275),
+    ]
+    .map(|d| SpatialInertia(Matrix6::from_diagonal(&d)))
+    .to_vec()
+}
+
+pub(crate) fn textbook_slist() -> Matrix6x3<f64> {
+    Matrix6x3::from_columns(&[
+        Vector6::new(1.0, 0.0, 1.0, 0.0, 1.0, 0.0),
+        Vector6::new(0.0, 1.0, 0.0, -0.089, 0.0, 0.0),
+        Vector6::new(0.0, 1.0, 0.0, -0.089, 0.0, 0.425),
+    ])
+}
+
+pub(crate) fn textbook_chain() -> DynamicChain<f64, U3> {
+    DynamicChain::from_mlist(&textbook_mlist(), &textbook_glist(), textbook_slist())
+}
+
+// Long chain alternating joint axes, with offset centres of mass and a prismatic joint
+pub(crate) fn long_chain(n: usize) -> DynamicChain<f64, Dyn> {
+    let mut frames = Vec::new();
+    let mut screws = Vec::new();
+    let mut inertias = Vec::new();
+    for i in 0..n {
+        let p = Vector3::new(0.3 * i as f64, 0.05 * (i % 3) as f64, 0.1);
+        let w = [Vector3::z(), Vector3::y(), Vector3::x()][i % 3];
+        frames.push(Matrix3::identity().to_so3().to_se3(p));
+        screws.push(if i == 2 {
+            Vector6::new(0.0, 0.0, 0.0, w[0], w[1], w[2])
+        } else {
+            let v = -w.cross(&p);
+            Vector6::new(w[0], w[1], w[2], v[0], v[1], v[2])
+        });
+        let inertia = Matrix3::from_diagonal(&Vector3::new(0.02, 0.03, 0.04));
+        inertias.push(SpatialInertia::from_mass_com(
+            1.0 + 0.1 * i as f64,
+            &Vector3::new(0.15, 0.01, -0.02),
+            &inertia,
+        ));
+    }
+    let tip = Matrix3::identity()
+        .to_so3()
+        .to_se3(Vector3::new(0.3 * n as f64, 0.0, 0.1));
+    DynamicChain::new(
+        OMatrix::<f64, U6, Dyn>::from_columns(&screws),
+        DVector::from_vec(frames),
+        tip,
+        DVector::from_vec(inertias),
+    )
+}
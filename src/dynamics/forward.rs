@@ -0,0 +1,84 @@
+extern crate nalgebra as na;
+use crate::dynamics::{DynamicChain, SpatialInertia};
+use crate::groups::{Se3Matrix, Wrench};
+use crate::interfaces::Numeric;
+use na::allocator::Allocator;
+use na::{DefaultAllocator, Dim, OVector, RealField, Vector3, U1, U6};
+
+impl<T: Numeric<T> + RealField, N: Dim> DynamicChain<T, N>
+where
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N>
+        + Allocator<T, N>
+        + Allocator<T, N, N>
+        + Allocator<Se3Matrix<T>, N>
+        + Allocator<SpatialInertia<T>, N>,
+{
+    // Solves M(theta) ddtheta = tau - c - g - J^T f_tip, None when M is not positive definite
+    pub fn forward_dynamics(
+        &self,
+        theta: &OVector<T, N>,
+        dtheta: &OVector<T, N>,
+        tau: &OVector<T, N>,
+        g: &Vector3<T>,
+        f_tip: &Wrench<T>,
+    ) -> Option<OVector<T, N>> {
+        let (n, _) = theta.shape_generic();
+        // With zero acceleration the recursion gives c + g + J^T f_tip in one pass
+        let bias = self.inverse_dynamics(theta, dtheta, &OVector::zeros_generic(n, U1), g, f_tip);
+        let chol = self.mass_matrix(theta).cholesky()?;
+        Some(chol.solve(&(tau - bias)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::fixtures::textbook_chain;
+    use crate::helpers::near_zero;
+    use na::{Vector6, U3};
+
+    #[test]
+    fn forward_dynamics_textbook() {
+        let ddtheta = textbook_chain()
+            .forward_dynamics(
+                &Vector3::new(0.1, 0.1, 0.1),
+                &Vector3::new(0.1, 0.2, 0.3),
+                &Vector3::new(0.5, 0.6, 0.7),
+                &Vector3::new(0.0, 0.0, -9.8),
+                &Wrench(Vector6::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0)),
+            )
+            .unwrap();
+        let expected = Vector3::new(-0.97392907, 25.58466784, -32.91499212);
+        assert!(near_zero((ddtheta - expected).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn forward_inverts_inverse_dynamics() {
+        let chain = textbook_chain();
+        let (theta, dtheta) = (Vector3::new(0.4, -0.3, 1.2), Vector3::new(-0.5, 0.2, 0.8));
+        let ddtheta = Vector3::new(1.0, -2.0, 0.5);
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let f_tip = Wrench(Vector6::new(0.1, -0.2, 0.3, 1.0, 2.0, -1.0));
+        let tau = chain.inverse_dynamics(&theta, &dtheta, &ddtheta, &g, &f_tip);
+        let recovered = chain
+            .forward_dynamics(&theta, &dtheta, &tau, &g, &f_tip)
+            .unwrap();
+        assert!(near_zero((recovered - ddtheta).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn forward_dynamics_massless_link() {
+        let mut chain = textbook_chain();
+        chain.inertias[2] = SpatialInertia::zeros();
+        let zero = Vector3::zeros();
+        let ddtheta = chain.forward_dynamics(
+            &zero,
+            &zero,
+            &zero,
+            &Vector3::new(0.0, 0.0, -9.8),
+            &Wrench(Vector6::zeros()),
+        );
+        assert_eq!(ddtheta, None::<OVector<f64, U3>>);
+    }
+}
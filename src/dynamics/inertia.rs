@@ -0,0 +1,225 @@
+extern crate nalgebra as na;
+use crate::errors::InertiaError;
+use crate::groups::{Adjoint, Inverse, Se3Matrix, So3Skew, ToSkew, ToVec, Twist, Wrench};
+use crate::interfaces::Numeric;
+use na::{Matrix3, Matrix6, RealField, Vector3};
+use std::ops::Add;
+
+// 6x6 spatial inertia acting on twists ordered (omega, v)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpatialInertia<T: Numeric<T>>(pub Matrix6<T>);
+
+impl<T: Numeric<T> + RealField> SpatialInertia<T> {
+    pub fn zeros() -> Self {
+        SpatialInertia(Matrix6::zeros())
+    }
+
+    // inertia is the rotational inertia about the centre of mass, in this frame's axes
+    pub fn from_mass_com(mass: T, com: &Vector3<T>, inertia: &Matrix3<T>) -> Self {
+        let c = com.to_skew().0;
+        let mut g = Matrix6::zeros();
+        g.fixed_view_mut::<3, 3>(0, 0)
+            .copy_from(&(inertia - c * c * mass));
+        g.fixed_view_mut::<3, 3>(0, 3).copy_from(&(c * mass));
+        g.fixed_view_mut::<3, 3>(3, 0).copy_from(&(-c * mass));
+        g.fixed_view_mut::<3, 3>(3, 3)
+            .copy_from(&(Matrix3::identity() * mass));
+        SpatialInertia(g)
+    }
+
+    pub fn point_mass(mass: T, com: &Vector3<T>) -> Self {
+        SpatialInertia::from_mass_com(mass, com, &Matrix3::zeros())
+    }
+
+    pub fn mass(&self) -> T {
+        self.0[(3, 3)]
+    }
+
+    pub fn center_of_mass(&self) -> Vector3<T> {
+        if self.mass() == T::zero() {
+            return Vector3::zeros();
+        }
+        So3Skew(self.0.fixed_view::<3, 3>(0, 3) / self.mass()).to_vec()
+    }
+
+    // Rotational inertia about the centre of mass
+    pub fn rotational_inertia(&self) -> Matrix3<T> {
+        let c = self.center_of_mass().to_skew().0;
+        self.0.fixed_view::<3, 3>(0, 0) + c * c * self.mass()
+    }
+
+    // self is expressed in {b}, t_ab is the pose of {b} in {a}, the result is expressed in {a}
+    pub fn change_frame(&self, t_ab: &Se3Matrix<T>) -> Self {
+        let ad = t_ab.inv().adjoint();
+        SpatialInertia(ad.transpose() * self.0 * ad)
+    }
+
+    pub fn momentum(&self, twist: &Twist<T>) -> Wrench<T> {
+        Wrench(self.0 * twist.0)
+    }
+
+    pub fn kinetic_energy(&self, twist: &Twist<T>) -> T {
+        twist.0.dot(&(self.0 * twist.0)) * na::convert(0.5)
+    }
+
+    pub fn validate(&self) -> Result<(), InertiaError> {
+        let tol: T = na::convert(1e-9);
+        let scale = self.0.abs().max().max(T::one());
+        if self.mass() <= T::zero() {
+            return Err(InertiaError::NonPositiveMass);
+        }
+        let mass_block = self.0.fixed_view::<3, 3>(3, 3) - Matrix3::identity() * self.mass();
+        if (self.0 - self.0.transpose()).abs().max() > tol * scale
+            || mass_block.abs().max() > tol * scale
+        {
+            return Err(InertiaError::NotSymmetric);
+        }
+        let moments = self.rotational_inertia().symmetric_eigenvalues();
+        if moments.iter().any(|&m| m <= T::zero()) {
+            return Err(InertiaError::NotPositiveDefinite);
+        }
+        let total = moments.sum();
+        if moments.iter().any(|&m| m > total - m + tol * scale) {
+            return Err(InertiaError::TriangleInequality);
+        }
+        Ok(())
+    }
+
+    pub fn is_physical(&self) -> bool {
+        self.validate().is_ok()
+    }
+}
+
+impl<T: Numeric<T> + RealField> Add for SpatialInertia<T> {
+    type Output = SpatialInertia<T>;
+
+    // Both inertias must be expressed in the same frame
+    fn add(self, rhs: SpatialInertia<T>) -> SpatialInertia<T> {
+        SpatialInertia(self.0 + rhs.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::{MatrixExp, So3ToSe3, ToSo3, ToTwist};
+    use crate::helpers::near_zero;
+    use na::Vector6;
+
+    fn body() -> SpatialInertia<f64> {
+        let inertia = Matrix3::new(0.3, 0.01, 0.0, 0.01, 0.2, 0.02, 0.0, 0.02, 0.25);
+        SpatialInertia::from_mass_com(2.0, &Vector3::new(0.1, -0.2, 0.3), &inertia)
+    }
+
+    #[test]
+    fn inertia_accessors() {
+        let g = body();
+        assert!(near_zero(g.mass() - 2.0, Some(1e-12)));
+        assert!(near_zero(
+            (g.center_of_mass() - Vector3::new(0.1, -0.2, 0.3)).norm(),
+            Some(1e-12)
+        ));
+        let inertia = Matrix3::new(0.3, 0.01, 0.0, 0.01, 0.2, 0.02, 0.0, 0.02, 0.25);
+        assert!(near_zero(
+            (g.rotational_inertia() - inertia).norm(),
+            Some(1e-12)
+        ));
+        assert!(g.is_physical());
+    }
+
+    #[test]
+    fn inertia_parallel_axis() {
+        let (m, c) = (3.0, Vector3::new(0.0, 0.5, 0.0));
+        let g = SpatialInertia::from_mass_com(m, &c, &Matrix3::identity());
+        let expected =
+            Matrix3::identity() + (Matrix3::identity() * c.dot(&c) - c * c.transpose()) * m;
+        assert!(near_zero(
+            (g.0.fixed_view::<3, 3>(0, 0) - expected).norm(),
+            Some(1e-12)
+        ));
+        // Moving the centre-of-mass frame by c gives the same inertia
+        let centred = SpatialInertia::from_mass_com(m, &Vector3::zeros(), &Matrix3::identity());
+        let t = Matrix3::identity().to_so3().to_se3(c);
+        assert!(near_zero(
+            (centred.change_frame(&t).0 - g.0).norm(),
+            Some(1e-12)
+        ));
+    }
+
+    #[test]
+    fn inertia_change_frame_rotation() {
+        let g = body();
+        let r = Vector3::new(0.3, -0.4, 0.5).to_skew().exp();
+        let t = r.to_se3(Vector3::new(1.0, 2.0, -1.0));
+        let moved = g.change_frame(&t);
+        assert!(near_zero(moved.mass() - 2.0, Some(1e-12)));
+        let com = r.0 * g.center_of_mass() + Vector3::new(1.0, 2.0, -1.0);
+        assert!(near_zero(
+            (moved.center_of_mass() - com).norm(),
+            Some(1e-12)
+        ));
+        let inertia = r.0 * g.rotational_inertia() * r.0.transpose();
+        assert!(near_zero(
+            (moved.rotational_inertia() - inertia).norm(),
+            Some(1e-12)
+        ));
+        // Kinetic energy does not depend on the frame it is computed in
+        let v_b = Vector6::new(0.1, 0.2, -0.3, 0.4, 0.5, 0.6).to_twist();
+        let v_a = t.transform_twist(&v_b);
+        assert!(near_zero(
+            g.kinetic_energy(&v_b) - moved.kinetic_energy(&v_a),
+            Some(1e-12)
+        ));
+    }
+
+    #[test]
+    fn inertia_sum_of_point_masses() {
+        let a = SpatialInertia::point_mass(1.0, &Vector3::new(1.0, 0.0, 0.0));
+        let b = SpatialInertia::point_mass(3.0, &Vector3::new(-1.0, 0.0, 0.0));
+        let sum = a + b;
+        assert!(near_zero(sum.mass() - 4.0, Some(1e-12)));
+        assert!(near_zero(
+            (sum.center_of_mass() - Vector3::new(-0.5, 0.0, 0.0)).norm(),
+            Some(1e-12)
+        ));
+        // Two masses on a line about their centre of mass: 1 * 1.5^2 + 3 * 0.5^2 = 3
+        let expected = Matrix3::from_diagonal(&Vector3::new(0.0, 3.0, 3.0));
+        assert!(near_zero(
+            (sum.rotational_inertia() - expected).norm(),
+            Some(1e-12)
+        ));
+    }
+
+    #[test]
+    fn inertia_validation() {
+        let inertia = Matrix3::from_diagonal(&Vector3::new(1.0, 1.0, 1.0));
+        assert_eq!(
+            SpatialInertia::from_mass_com(0.0, &Vector3::zeros(), &inertia).validate(),
+            Err(InertiaError::NonPositiveMass)
+        );
+        let flat = Matrix3::from_diagonal(&Vector3::new(1.0, 1.0, -0.1));
+        assert_eq!(
+            SpatialInertia::from_mass_com(1.0, &Vector3::zeros(), &flat).validate(),
+            Err(InertiaError::NotPositiveDefinite)
+        );
+        let thin = Matrix3::from_diagonal(&Vector3::new(1.0, 1.0, 3.0));
+        assert_eq!(
+            SpatialInertia::from_mass_com(1.0, &Vector3::zeros(), &thin).validate(),
+            Err(InertiaError::TriangleInequality)
+        );
+        let mut skewed = body();
+        skewed.0[(0, 4)] += 0.1;
+        assert_eq!(skewed.validate(), Err(InertiaError::NotSymmetric));
+    }
+
+    #[test]
+    fn inertia_momentum() {
+        let g = body();
+        let v = Vector6::new(0.1, 0.2, -0.3, 0.4, 0.5, 0.6).to_twist();
+        let h = g.momentum(&v);
+        assert!(near_zero(
+            h.0.dot(&v.0) * 0.5 - g.kinetic_energy(&v),
+            Some(1e-12)
+        ));
+    }
+}
@@ -0,0 +1,272 @@
+extern crate nalgebra as na;
+use crate::dynamics::SpatialInertia;
+use crate::groups::{CoAd, MatrixExp, Se3Matrix, ToSo3, ToTwist, Twist, Wrench};
+use crate::interfaces::Numeric;
+use na::allocator::Allocator;
+use na::{DefaultAllocator, Dim, OVector, RealField, Vector3, Vector6};
+
+// Explicit Euler, positions advance with the old velocities
+pub fn euler_step<T, N>(
+    theta: &OVector<T, N>,
+    dtheta: &OVector<T, N>,
+    ddtheta: &OVector<T, N>,
+    dt: T,
+) -> (OVector<T, N>, OVector<T, N>)
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    DefaultAllocator: Allocator<T, N>,
+{
+    (theta + dtheta * dt, dtheta + ddtheta * dt)
+}
+
+// Semi-implicit (symplectic) Euler, positions advance with the new velocities
+pub fn semi_implicit_euler_step<T, N>(
+    theta: &OVector<T, N>,
+    dtheta: &OVector<T, N>,
+    ddtheta: &OVector<T, N>,
+    dt: T,
+) -> (OVector<T, N>, OVector<T, N>)
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    DefaultAllocator: Allocator<T, N>,
+{
+    let dtheta_next = dtheta + ddtheta * dt;
+    (theta + &dtheta_next * dt, dtheta_next)
+}
+
+// Classic fourth order Runge-Kutta, accel maps (theta, dtheta) to ddtheta
+pub fn rk4_step<T, N, F>(
+    theta: &OVector<T, N>,
+    dtheta: &OVector<T, N>,
+    dt: T,
+    mut accel: F,
+) -> (OVector<T, N>, OVector<T, N>)
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    F: FnMut(&OVector<T, N>, &OVector<T, N>) -> OVector<T, N>,
+    DefaultAllocator: Allocator<T, N>,
+{
+    let half = dt * na::convert(0.5);
+    let k1 = (dtheta.clone_owned(), accel(theta, dtheta));
+    let (t2, v2) = (theta + &k1.0 * half, dtheta + &k1.1 * half);
+    let k2 = (v2.clone(), accel(&t2, &v2));
+    let (t3, v3) = (theta + &k2.0 * half, dtheta + &k2.1 * half);
+    let k3 = (v3.clone(), accel(&t3, &v3));
+    let (t4, v4) = (theta + &k3.0 * dt, dtheta + &k3.1 * dt);
+    let k4 = (v4.clone(), accel(&t4, &v4));
+    let sixth = dt / na::convert(6.0);
+    let two: T = na::convert(2.0);
+    (
+        theta + (k1.0 + k2.0 * two + k3.0 * two + k4.0) * sixth,
+        dtheta + (k1.1 + k2.1 * two + k3.1 * two + k4.1) * sixth,
+    )
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Integrator {
+    Euler,
+    SemiImplicitEuler,
+    Rk4,
+}
+
+impl Integrator {
+    pub fn step<T, N, F>(
+        &self,
+        theta: &OVector<T, N>,
+        dtheta: &OVector<T, N>,
+        dt: T,
+        mut accel: F,
+    ) -> (OVector<T, N>, OVector<T, N>)
+    where
+        T: Numeric<T> + RealField,
+        N: Dim,
+        F: FnMut(&OVector<T, N>, &OVector<T, N>) -> OVector<T, N>,
+        DefaultAllocator: Allocator<T, N>,
+    {
+        match self {
+            Integrator::Euler => euler_step(theta, dtheta, &accel(theta, dtheta), dt),
+            Integrator::SemiImplicitEuler => {
+                semi_implicit_euler_step(theta, dtheta, &accel(theta, dtheta), dt)
+            }
+            Integrator::Rk4 => rk4_step(theta, dtheta, dt, accel),
+        }
+    }
+}
+
+// Moves a pose along a body frame twist for dt, exactly on SE(3)
+pub fn lie_group_step<T>(pose: &Se3Matrix<T>, twist: &Twist<T>, dt: T) -> Se3Matrix<T>
+where
+    T: Numeric<T> + RealField,
+    f64: From<T>,
+{
+    *pose * (twist.0 * dt).to_twist().exp()
+}
+
+// Unconstrained rigid body, twist is expressed in the body frame
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FreeBody<T: Numeric<T>> {
+    pub inertia: SpatialInertia<T>,
+    pub pose: Se3Matrix<T>,
+    pub twist: Twist<T>,
+}
+
+impl<T: Numeric<T> + RealField> FreeBody<T>
+where
+    f64: From<T>,
+{
+    pub fn new(inertia: SpatialInertia<T>, pose: Se3Matrix<T>, twist: Twist<T>) -> Self {
+        FreeBody {
+            inertia,
+            pose,
+            twist,
+        }
+    }
+
+    // Body frame acceleration from G dV = F + ad_V^T G V with gravity g given in the space frame,
+    // None when G is not positive definite
+    pub fn acceleration(&self, wrench: &Wrench<T>, g: &Vector3<T>) -> Option<Twist<T>> {
+        let g_body = self.pose.to_so3().0.transpose() * g;
+        let gravity = Vector6::new(
+            T::zero(),
+            T::zero(),
+            T::zero(),
+            g_body[0],
+            g_body[1],
+            g_body[2],
+        );
+        let bias = self.inertia.momentum(&self.twist).coad(&self.twist);
+        let f = wrench.0 + bias.0 + self.inertia.0 * gravity;
+        let chol = self.inertia.0.cholesky()?;
+        Some(chol.solve(&f).to_twist())
+    }
+
+    // Semi-implicit step, the pose moves with the updated twist through the exponential map
+    pub fn step(&self, wrench: &Wrench<T>, g: &Vector3<T>, dt: T) -> Option<Self> {
+        let twist = Twist(self.twist.0 + self.acceleration(wrench, g)?.0 * dt);
+        Some(FreeBody::new(
+            self.inertia,
+            lie_group_step(&self.pose, &twist, dt),
+            twist,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::{Se3Distance, ToSe3};
+    use crate::helpers::near_zero;
+    use na::{Matrix3, Matrix4, Vector1};
+
+    #[test]
+    fn euler_step_textbook() {
+        let (theta, dtheta) = euler_step(
+            &Vector3::new(0.1, 0.1, 0.1),
+            &Vector3::new(0.1, 0.2, 0.3),
+            &Vector3::new(2.0, 1.5, 1.0),
+            0.1,
+        );
+        assert!(near_zero(
+            (theta - Vector3::new(0.11, 0.12, 0.13)).norm(),
+            Some(1e-12)
+        ));
+        assert!(near_zero(
+            (dtheta - Vector3::new(0.3, 0.35, 0.4)).norm(),
+            Some(1e-12)
+        ));
+    }
+
+    // theta'' = -theta from (1, 0) for 10 s, energy is 1/2 throughout
+    fn oscillator_energy<F>(mut step: F) -> f64
+    where
+        F: FnMut(&Vector1<f64>, &Vector1<f64>) -> (Vector1<f64>, Vector1<f64>),
+    {
+        let (mut theta, mut dtheta) = (Vector1::new(1.0), Vector1::new(0.0));
+        for _ in 0..1000 {
+            (theta, dtheta) = step(&theta, &dtheta);
+        }
+        0.5 * (theta[0] * theta[0] + dtheta[0] * dtheta[0])
+    }
+
+    #[test]
+    fn integrators_on_oscillator() {
+        let dt = 0.01;
+        let explicit = oscillator_energy(|t, v| euler_step(t, v, &-t, dt));
+        let symplectic = oscillator_energy(|t, v| semi_implicit_euler_step(t, v, &-t, dt));
+        let rk4 = oscillator_energy(|t, v| rk4_step(t, v, dt, |t, _| -t));
+        // Explicit Euler gains energy, the others stay close to 1/2
+        assert!(explicit > 0.55);
+        assert!((symplectic - 0.5).abs() < 0.01);
+        assert!((rk4 - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rk4_matches_solution() {
+        let (theta, dtheta) = rk4_step(&Vector1::new(1.0), &Vector1::new(0.0), 0.1, |t, _| -t);
+        assert!(near_zero(theta[0] - 0.1f64.cos(), Some(1e-7)));
+        assert!(near_zero(dtheta[0] + 0.1f64.sin(), Some(1e-7)));
+    }
+
+    #[test]
+    fn lie_group_step_constant_twist() {
+        let twist = Vector6::new(0.3, -0.2, 0.5, 1.0, 0.0, -0.5).to_twist();
+        let mut pose = Matrix4::identity().to_se3();
+        for _ in 0..1000 {
+            pose = lie_group_step(&pose, &twist, 0.01);
+        }
+        let exact = (twist.0 * 10.0).to_twist().exp();
+        assert!(near_zero((pose.0 - exact.0).norm(), Some(1e-9)));
+        assert!(pose.distance_to_se3() < 1e-9);
+    }
+
+    #[test]
+    fn free_body_stays_on_se3() {
+        let inertia = SpatialInertia::from_mass_com(
+            2.0,
+            &Vector3::zeros(),
+            &Matrix3::from_diagonal(&Vector3::new(0.1, 0.2, 0.3)),
+        );
+        let twist = Vector6::new(0.1, 3.0, 0.1, 0.0, 0.0, 0.0).to_twist();
+        let mut body = FreeBody::new(inertia, Matrix4::identity().to_se3(), twist);
+        let none = Wrench(Vector6::zeros());
+        let energy: f64 = inertia.kinetic_energy(&body.twist);
+        for _ in 0..2000 {
+            body = body.step(&none, &Vector3::zeros(), 0.001).unwrap();
+        }
+        assert!(body.pose.distance_to_se3() < 1e-9);
+        assert!((inertia.kinetic_energy(&body.twist) - energy).abs() / energy < 1e-2);
+    }
+
+    #[test]
+    fn free_body_falls() {
+        let inertia = SpatialInertia::from_mass_com(1.0, &Vector3::zeros(), &Matrix3::identity());
+        let pose = Vector6::new(0.3, 0.2, 0.1, 0.0, 0.0, 0.0).to_twist().exp();
+        let body = FreeBody::new(inertia, pose, Vector6::zeros().to_twist());
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let accel = body.acceleration(&Wrench(Vector6::zeros()), &g).unwrap();
+        let space = pose.to_so3().0 * accel.0.fixed_view::<3, 1>(3, 0);
+        assert!(near_zero((space - g).norm(), Some(1e-9)));
+        assert!(near_zero(
+            accel.0.fixed_view::<3, 1>(0, 0).norm(),
+            Some(1e-12)
+        ));
+    }
+
+    #[test]
+    fn free_body_without_inertia() {
+        let body = FreeBody::new(
+            SpatialInertia::zeros(),
+            Matrix4::identity().to_se3(),
+            Vector6::zeros().to_twist(),
+        );
+        let none = Wrench(Vector6::zeros());
+        assert_eq!(
+            body.acceleration(&none, &Vector3::new(0.0, 0.0, -9.8)),
+            None
+        );
+        assert_eq!(body.step(&none, &Vector3::zeros(), 0.01), None);
+    }
+}
@@ -0,0 +1,17 @@
+mod aba;
+mod chain;
+#[cfg(test)]
+mod fixtures;
+mod forward;
+mod inertia;
+mod integrate;
+mod rnea;
+mod terms;
+mod trajectory;
+
+pub use chain::DynamicChain;
+pub use inertia::SpatialInertia;
+pub use integrate::{
+    euler_step, lie_group_step, rk4_step, semi_implicit_euler_step, FreeBody, Integrator,
+};
+pub use rnea::inverse_dynamics;
@@ -0,0 +1,182 @@
+extern crate nalgebra as na;
+use crate::dynamics::{DynamicChain, SpatialInertia};
+use crate::groups::{Ad, Adjoint, CoAd, Inverse, MatrixExp, Power, Se3Matrix, ToTwist, Wrench};
+use crate::interfaces::Numeric;
+use na::allocator::Allocator;
+use na::{DefaultAllocator, Dim, Matrix4, OMatrix, OVector, RealField, Vector3, Vector6, U1, U6};
+
+// Textbook InverseDynamics signature, mlist holds the n+1 relative frames M_(i-1)i
+#[allow(clippy::too_many_arguments)]
+pub fn inverse_dynamics<T, N>(
+    theta: &OVector<T, N>,
+    dtheta: &OVector<T, N>,
+    ddtheta: &OVector<T, N>,
+    g: &Vector3<T>,
+    f_tip: &Wrench<T>,
+    mlist: &[Se3Matrix<T>],
+    glist: &[SpatialInertia<T>],
+    slist: &OMatrix<T, U6, N>,
+) -> OVector<T, N>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N>
+        + Allocator<T, N>
+        + Allocator<Se3Matrix<T>, N>
+        + Allocator<SpatialInertia<T>, N>,
+{
+    DynamicChain::from_mlist(mlist, glist, slist.clone_owned())
+        .inverse_dynamics(theta, dtheta, ddtheta, g, f_tip)
+}
+
+impl<T: Numeric<T> + RealField, N: Dim> DynamicChain<T, N>
+where
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N>
+        + Allocator<T, N>
+        + Allocator<Se3Matrix<T>, N>
+        + Allocator<SpatialInertia<T>, N>,
+{
+    // Recursive Newton-Euler, f_tip is the wrench the end-effector applies in its own frame
+    pub fn inverse_dynamics(
+        &self,
+        theta: &OVector<T, N>,
+        dtheta: &OVector<T, N>,
+        ddtheta: &OVector<T, N>,
+        g: &Vector3<T>,
+        f_tip: &Wrench<T>,
+    ) -> OVector<T, N> {
+        let n = self.dof();
+        let (ncols, _) = theta.shape_generic();
+        let a = self.link_screws();
+        let mut twists = OMatrix::zeros_generic(U6, ncols);
+        let mut accels = OMatrix::zeros_generic(U6, ncols);
+        // T_i(i-1), pose of {i-1} seen from {i}
+        let mut frames = OVector::from_element_generic(ncols, U1, Se3Matrix(Matrix4::identity()));
+
+        let mut v = Vector6::zeros();
+        // Gravity enters as an upward acceleration of the base
+        let mut vdot = Vector6::new(T::zero(), T::zero(), T::zero(), -g[0], -g[1], -g[2]);
+        for i in 0..n {
+            let ai: Vector6<T> = a.column(i).into_owned();
+            let t = (ai * -theta[i]).to_twist().exp() * self.relative_frame(i).inv();
+            let ad = t.adjoint();
+            v = ad * v + ai * dtheta[i];
+            vdot = ad * vdot + v.to_twist().ad() * ai * dtheta[i] + ai * ddtheta[i];
+            twists.set_column(i, &v);
+            accels.set_column(i, &vdot);
+            frames[i] = t;
+        }
+
+        let mut tau = OVector::zeros_generic(ncols, U1);
+        let mut f = *f_tip;
+        let mut next = self.relative_frame(n).inv();
+        for i in (0..n).rev() {
+            let vi = twists.column(i).into_owned().to_twist();
+            let gi = self.inertias[i];
+            let momentum = gi.momentum(&vi);
+            f = Wrench(next.coadjoint() * f.0 + gi.0 * accels.column(i) - momentum.coad(&vi).0);
+            tau[i] = f.power(&a.column(i).into_owned().to_twist());
+            next = frames[i];
+        }
+        tau
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::fixtures::{
+        textbook_chain, textbook_glist, textbook_mlist, textbook_slist,
+    };
+    use crate::groups::ToSe3;
+    use crate::helpers::near_zero;
+    use na::{DVector, Dyn, Vector1};
+
+    #[test]
+    fn inverse_dynamics_textbook() {
+        let tau = inverse_dynamics(
+            &Vector3::new(0.1, 0.1, 0.1),
+            &Vector3::new(0.1, 0.2, 0.3),
+            &Vector3::new(2.0, 1.5, 1.0),
+            &Vector3::new(0.0, 0.0, -9.8),
+            &Wrench(Vector6::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0)),
+            &textbook_mlist(),
+            &textbook_glist(),
+            &textbook_slist(),
+        );
+        let expected = Vector3::new(74.69616155, -33.06766016, -3.23057314);
+        assert!(near_zero((tau - expected).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn inverse_dynamics_dynamic_size() {
+        let slist = textbook_slist();
+        let dyn_slist = OMatrix::<f64, U6, Dyn>::from_column_slice(slist.as_slice());
+        let chain = DynamicChain::from_mlist(&textbook_mlist(), &textbook_glist(), dyn_slist);
+        let tau = chain.inverse_dynamics(
+            &DVector::from_vec(vec![0.1, 0.1, 0.1]),
+            &DVector::from_vec(vec![0.1, 0.2, 0.3]),
+            &DVector::from_vec(vec![2.0, 1.5, 1.0]),
+            &Vector3::new(0.0, 0.0, -9.8),
+            &Wrench(Vector6::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0)),
+        );
+        let expected = DVector::from_vec(vec![74.69616155, -33.06766016, -3.23057314]);
+        assert!(near_zero((tau - expected).norm(), Some(1e-6)));
+    }
+
+    // Point mass m at distance l from a joint about y
+    fn pendulum(m: f64, l: f64) -> DynamicChain<f64, U1> {
+        let frame = Matrix4::new(
+            1.0, 0.0, 0.0, l, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3();
+        DynamicChain::new(
+            na::Matrix6x1::new(0.0, 1.0, 0.0, 0.0, 0.0, 0.0),
+            OVector::<Se3Matrix<f64>, U1>::from_element(frame),
+            frame,
+            OVector::<SpatialInertia<f64>, U1>::from_element(SpatialInertia::point_mass(
+                m,
+                &Vector3::zeros(),
+            )),
+        )
+    }
+
+    #[test]
+    fn inverse_dynamics_pendulum() {
+        let (m, l, theta) = (2.0, 0.5, 0.3);
+        let chain = pendulum(m, l);
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let zero = Wrench(Vector6::zeros());
+        let hold = chain.inverse_dynamics(
+            &Vector1::new(theta),
+            &Vector1::zeros(),
+            &Vector1::zeros(),
+            &g,
+            &zero,
+        );
+        assert!(near_zero(hold[0] + m * 9.8 * l * theta.cos(), Some(1e-9)));
+        let accelerate = chain.inverse_dynamics(
+            &Vector1::new(theta),
+            &Vector1::new(1.5),
+            &Vector1::new(2.0),
+            &Vector3::zeros(),
+            &zero,
+        );
+        assert!(near_zero(accelerate[0] - m * l * l * 2.0, Some(1e-9)));
+    }
+
+    #[test]
+    fn dynamic_chain_frames() {
+        let chain = textbook_chain();
+        for (i, m) in textbook_mlist().iter().enumerate() {
+            assert!(near_zero(
+                (chain.relative_frame(i).0 - m.0).norm(),
+                Some(1e-12)
+            ));
+        }
+        let home = chain.to_serial_chain().fkin(&Vector3::zeros());
+        assert!(near_zero((home.0 - chain.tip.0).norm(), Some(1e-12)));
+    }
+}
@@ -0,0 +1,134 @@
+extern crate nalgebra as na;
+use crate::dynamics::{DynamicChain, SpatialInertia};
+use crate::groups::{Se3Matrix, Wrench};
+use crate::interfaces::Numeric;
+use na::allocator::Allocator;
+use na::{DefaultAllocator, Dim, OMatrix, OVector, RealField, Vector3, Vector6, U1, U6};
+
+// Each term is one pass of the Newton-Euler recursion with the other inputs set to zero
+impl<T: Numeric<T> + RealField, N: Dim> DynamicChain<T, N>
+where
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N>
+        + Allocator<T, N>
+        + Allocator<T, N, N>
+        + Allocator<Se3Matrix<T>, N>
+        + Allocator<SpatialInertia<T>, N>,
+{
+    pub fn mass_matrix(&self, theta: &OVector<T, N>) -> OMatrix<T, N, N> {
+        let (n, _) = theta.shape_generic();
+        let zeros = OVector::zeros_generic(n, U1);
+        let mut m = OMatrix::zeros_generic(n, n);
+        for i in 0..self.dof() {
+            let mut ddtheta = zeros.clone();
+            ddtheta[i] = T::one();
+            m.set_column(
+                i,
+                &self.inverse_dynamics(theta, &zeros, &ddtheta, &Vector3::zeros(), &no_wrench()),
+            );
+        }
+        m
+    }
+
+    // Coriolis and centripetal torques c(theta, dtheta)
+    pub fn vel_quadratic_forces(
+        &self,
+        theta: &OVector<T, N>,
+        dtheta: &OVector<T, N>,
+    ) -> OVector<T, N> {
+        let (n, _) = theta.shape_generic();
+        let zeros = OVector::zeros_generic(n, U1);
+        self.inverse_dynamics(theta, dtheta, &zeros, &Vector3::zeros(), &no_wrench())
+    }
+
+    pub fn gravity_forces(&self, theta: &OVector<T, N>, g: &Vector3<T>) -> OVector<T, N> {
+        let (n, _) = theta.shape_generic();
+        let zeros = OVector::zeros_generic(n, U1);
+        self.inverse_dynamics(theta, &zeros, &zeros, g, &no_wrench())
+    }
+
+    // J^T F_tip, the torques needed to apply f_tip at the end-effector
+    pub fn end_effector_forces(&self, theta: &OVector<T, N>, f_tip: &Wrench<T>) -> OVector<T, N> {
+        let (n, _) = theta.shape_generic();
+        let zeros = OVector::zeros_generic(n, U1);
+        self.inverse_dynamics(theta, &zeros, &zeros, &Vector3::zeros(), f_tip)
+    }
+}
+
+fn no_wrench<T: Numeric<T>>() -> Wrench<T> {
+    Wrench(Vector6::zeros())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::fixtures::textbook_chain;
+    use crate::helpers::near_zero;
+    use na::Matrix3;
+
+    fn theta() -> Vector3<f64> {
+        Vector3::new(0.1, 0.1, 0.1)
+    }
+
+    #[test]
+    fn mass_matrix_textbook() {
+        let m = textbook_chain().mass_matrix(&theta());
+        let expected = Matrix3::new(
+            22.5433380,
+            -0.307146754,
+            -0.00718426391,
+            -0.307146754,
+            1.96850717,
+            0.432157368,
+            -0.00718426391,
+            0.432157368,
+            0.191630858,
+        );
+        assert!(near_zero((m - expected).norm(), Some(1e-6)));
+        assert!(near_zero((m - m.transpose()).norm(), Some(1e-12)));
+        assert!(m.cholesky().is_some());
+    }
+
+    #[test]
+    fn vel_quadratic_forces_textbook() {
+        let c = textbook_chain().vel_quadratic_forces(&theta(), &Vector3::new(0.1, 0.2, 0.3));
+        let expected = Vector3::new(0.26453118, -0.05505157, -0.00689132);
+        assert!(near_zero((c - expected).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn gravity_forces_textbook() {
+        let g = textbook_chain().gravity_forces(&theta(), &Vector3::new(0.0, 0.0, -9.8));
+        let expected = Vector3::new(28.40331262, -37.64094817, -5.4415892);
+        assert!(near_zero((g - expected).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn end_effector_forces_textbook() {
+        let chain = textbook_chain();
+        let f_tip = Wrench(Vector6::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0));
+        let tau = chain.end_effector_forces(&theta(), &f_tip);
+        let expected = Vector3::new(1.40954608, 1.85771497, 1.392409);
+        assert!(near_zero((tau - expected).norm(), Some(1e-6)));
+        // Same as the body Jacobian transpose of the kinematic chain
+        let jb = chain.to_serial_chain().to_body().jacobian_body(&theta());
+        assert!(near_zero(
+            (tau - jb.transpose() * f_tip.0).norm(),
+            Some(1e-9)
+        ));
+    }
+
+    #[test]
+    fn terms_sum_to_inverse_dynamics() {
+        let chain = textbook_chain();
+        let (dtheta, ddtheta) = (Vector3::new(0.1, 0.2, 0.3), Vector3::new(2.0, 1.5, 1.0));
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let f_tip = Wrench(Vector6::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0));
+        let sum = chain.mass_matrix(&theta()) * ddtheta
+            + chain.vel_quadratic_forces(&theta(), &dtheta)
+            + chain.gravity_forces(&theta(), &g)
+            + chain.end_effector_forces(&theta(), &f_tip);
+        let tau = chain.inverse_dynamics(&theta(), &dtheta, &ddtheta, &g, &f_tip);
+        assert!(near_zero((sum - tau).norm(), Some(1e-9)));
+    }
+}
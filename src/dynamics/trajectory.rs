@@ -0,0 +1,270 @@
+extern crate nalgebra as na;
+use crate::dynamics::{DynamicChain, Integrator, SpatialInertia};
+use crate::groups::{Se3Matrix, Wrench};
+use crate::interfaces::Numeric;
+use na::allocator::Allocator;
+use na::{DefaultAllocator, Dim, Dyn, OMatrix, OVector, RealField, Vector3, Vector6, U1, U6};
+
+// Trajectories hold one sample per row, so an n joint chain uses N x n matrices
+impl<T: Numeric<T> + RealField, N: Dim> DynamicChain<T, N>
+where
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N>
+        + Allocator<T, N>
+        + Allocator<T, Dyn, N>
+        + Allocator<Se3Matrix<T>, N>
+        + Allocator<SpatialInertia<T>, N>,
+{
+    // Joint torques at each sample, every input matrix must have one row per sample
+    pub fn inverse_dynamics_trajectory(
+        &self,
+        thetamat: &OMatrix<T, Dyn, N>,
+        dthetamat: &OMatrix<T, Dyn, N>,
+        ddthetamat: &OMatrix<T, Dyn, N>,
+        g: &Vector3<T>,
+        ftipmat: &OMatrix<T, Dyn, U6>,
+    ) -> OMatrix<T, Dyn, N> {
+        let samples = thetamat.nrows();
+        for (name, rows) in [
+            ("dthetamat", dthetamat.nrows()),
+            ("ddthetamat", ddthetamat.nrows()),
+            ("ftipmat", ftipmat.nrows()),
+        ] {
+            assert_eq!(
+                rows, samples,
+                "inverse_dynamics_trajectory: expected {} rows in {}, got {}",
+                samples, name, rows
+            );
+        }
+        let mut taumat = thetamat.clone_owned();
+        for i in 0..thetamat.nrows() {
+            let tau = self.inverse_dynamics(
+                &thetamat.row(i).transpose(),
+                &dthetamat.row(i).transpose(),
+                &ddthetamat.row(i).transpose(),
+                g,
+                &tip_wrench(ftipmat, i),
+            );
+            taumat.row_mut(i).tr_copy_from(&tau);
+        }
+        taumat
+    }
+
+    // Torques and tip wrenches are held for dt per sample, integrated in `substeps` equal steps.
+    // Returns the joint positions and velocities at each sample, starting with the initial state.
+    // None when ftipmat and taumat differ in rows, dt is not positive, substeps is zero or the
+    // articulated-body algorithm fails along the way.
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    pub fn forward_dynamics_trajectory(
+        &self,
+        theta0: &OVector<T, N>,
+        dtheta0: &OVector<T, N>,
+        taumat: &OMatrix<T, Dyn, N>,
+        g: &Vector3<T>,
+        ftipmat: &OMatrix<T, Dyn, U6>,
+        dt: T,
+        substeps: usize,
+        integrator: Integrator,
+    ) -> Option<(OMatrix<T, Dyn, N>, OMatrix<T, Dyn, N>)> {
+        if ftipmat.nrows() != taumat.nrows() || !dt.is_finite() || dt <= T::zero() || substeps == 0
+        {
+            return None;
+        }
+        let mut thetamat = taumat.clone_owned();
+        let mut dthetamat = taumat.clone_owned();
+        let h = dt / na::convert(substeps as f64);
+        let mut theta = theta0.clone_owned();
+        let mut dtheta = dtheta0.clone_owned();
+        for i in 0..taumat.nrows() {
+            thetamat.row_mut(i).tr_copy_from(&theta);
+            dthetamat.row_mut(i).tr_copy_from(&dtheta);
+            if i + 1 == taumat.nrows() {
+                break;
+            }
+            let tau = taumat.row(i).transpose();
+            let f_tip = tip_wrench(ftipmat, i);
+            for _ in 0..substeps {
+                // The integrators take infallible accelerations, so a failure is flagged and
+                // replaced by zero until the step returns
+                let mut failed = false;
+                (theta, dtheta) = integrator.step(&theta, &dtheta, h, |t, v| {
+                    self.forward_dynamics_aba(t, v, &tau, g, &f_tip)
+                        .unwrap_or_else(|| {
+                            failed = true;
+                            OVector::zeros_generic(t.shape_generic().0, U1)
+                        })
+                });
+                if failed {
+                    return None;
+                }
+            }
+        }
+        Some((thetamat, dthetamat))
+    }
+}
+
+fn tip_wrench<T: Numeric<T>>(ftipmat: &OMatrix<T, Dyn, U6>, i: usize) -> Wrench<T> {
+    let row: Vector6<T> = ftipmat.row(i).transpose();
+    Wrench(row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::fixtures::textbook_chain;
+    use crate::helpers::near_zero;
+    use na::U3;
+
+    // theta_j(t) = sin((j + 1) t) / (j + 1) sampled every dt
+    fn trajectory(samples: usize, dt: f64) -> [OMatrix<f64, Dyn, U3>; 3] {
+        let sample = |f: fn(f64, f64) -> f64| {
+            OMatrix::<f64, Dyn, U3>::from_fn(samples, |i, j| f(i as f64 * dt, j as f64 + 1.0))
+        };
+        [
+            sample(|t, w| (w * t).sin() / w),
+            sample(|t, w| (w * t).cos()),
+            sample(|t, w| -w * (w * t).sin()),
+        ]
+    }
+
+    #[test]
+    fn inverse_dynamics_trajectory_rows() {
+        let chain = textbook_chain();
+        let [theta, dtheta, ddtheta] = trajectory(20, 0.05);
+        let ftip = OMatrix::<f64, Dyn, U6>::from_fn(20, |i, j| 0.1 * (i + j) as f64);
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let taumat = chain.inverse_dynamics_trajectory(&theta, &dtheta, &ddtheta, &g, &ftip);
+        assert_eq!(taumat.shape(), (20, 3));
+        for i in [0, 7, 19] {
+            let tau = chain.inverse_dynamics(
+                &theta.row(i).transpose(),
+                &dtheta.row(i).transpose(),
+                &ddtheta.row(i).transpose(),
+                &g,
+                &Wrench(ftip.row(i).transpose()),
+            );
+            assert!(near_zero(
+                (taumat.row(i).transpose() - tau).norm(),
+                Some(1e-12)
+            ));
+        }
+    }
+
+    #[test]
+    fn forward_dynamics_trajectory_holds_still() {
+        let chain = textbook_chain();
+        let theta0 = Vector3::new(0.2, -0.4, 0.6);
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let hold = chain.gravity_forces(&theta0, &g).transpose();
+        let taumat = OMatrix::<f64, Dyn, U3>::from_fn(10, |_, j| hold[j]);
+        let ftip = OMatrix::<f64, Dyn, U6>::zeros(10);
+        for integrator in [
+            Integrator::Euler,
+            Integrator::SemiImplicitEuler,
+            Integrator::Rk4,
+        ] {
+            let (thetamat, dthetamat) = chain
+                .forward_dynamics_trajectory(
+                    &theta0,
+                    &Vector3::zeros(),
+                    &taumat,
+                    &g,
+                    &ftip,
+                    0.1,
+                    4,
+                    integrator,
+                )
+                .unwrap();
+            assert_eq!(thetamat.shape(), (10, 3));
+            assert!(near_zero(
+                (thetamat.row(9).transpose() - theta0).norm(),
+                Some(1e-9)
+            ));
+            assert!(near_zero(dthetamat.row(9).norm(), Some(1e-9)));
+        }
+    }
+
+    #[test]
+    fn forward_dynamics_trajectory_substeps_converge() {
+        let chain = textbook_chain();
+        let (samples, dt) = (11, 0.05);
+        let [theta, dtheta, ddtheta] = trajectory(samples, dt);
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let ftip = OMatrix::<f64, Dyn, U6>::zeros(samples);
+        let taumat = chain.inverse_dynamics_trajectory(&theta, &dtheta, &ddtheta, &g, &ftip);
+        let theta0 = theta.row(0).transpose();
+        let dtheta0 = dtheta.row(0).transpose();
+        let simulate = |substeps, integrator| {
+            chain
+                .forward_dynamics_trajectory(
+                    &theta0, &dtheta0, &taumat, &g, &ftip, dt, substeps, integrator,
+                )
+                .unwrap()
+                .0
+        };
+        let reference = simulate(20, Integrator::Rk4);
+        let coarse = (simulate(1, Integrator::Euler) - &reference).norm();
+        let fine = (simulate(32, Integrator::Euler) - &reference).norm();
+        assert!(fine < coarse / 10.0);
+        // Held torques only approximate the smooth trajectory, to first order in dt
+        assert!((reference - theta).norm() < 0.05);
+    }
+
+    #[test]
+    fn forward_dynamics_trajectory_massless_link() {
+        let mut chain = textbook_chain();
+        chain.inertias[2] = SpatialInertia::zeros();
+        let taumat = OMatrix::<f64, Dyn, U3>::zeros(5);
+        let ftip = OMatrix::<f64, Dyn, U6>::zeros(5);
+        let res = chain.forward_dynamics_trajectory(
+            &Vector3::zeros(),
+            &Vector3::zeros(),
+            &taumat,
+            &Vector3::new(0.0, 0.0, -9.8),
+            &ftip,
+            0.1,
+            2,
+            Integrator::Rk4,
+        );
+        assert_eq!(res, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 20 rows in ddthetamat, got 19")]
+    fn inverse_dynamics_trajectory_row_mismatch() {
+        let [theta, dtheta, ddtheta] = trajectory(20, 0.05);
+        let ftip = OMatrix::<f64, Dyn, U6>::zeros(20);
+        textbook_chain().inverse_dynamics_trajectory(
+            &theta,
+            &dtheta,
+            &ddtheta.rows(0, 19).into_owned(),
+            &Vector3::new(0.0, 0.0, -9.8),
+            &ftip,
+        );
+    }
+
+    #[test]
+    fn forward_dynamics_trajectory_invalid_arguments() {
+        let chain = textbook_chain();
+        let taumat = OMatrix::<f64, Dyn, U3>::zeros(5);
+        let g = Vector3::new(0.0, 0.0, -9.8);
+        let simulate = |ftip_rows, dt, substeps| {
+            chain.forward_dynamics_trajectory(
+                &Vector3::zeros(),
+                &Vector3::zeros(),
+                &taumat,
+                &g,
+                &OMatrix::<f64, Dyn, U6>::zeros(ftip_rows),
+                dt,
+                substeps,
+                Integrator::Euler,
+            )
+        };
+        assert!(simulate(5, 0.1, 2).is_some());
+        assert_eq!(simulate(4, 0.1, 2), None);
+        assert_eq!(simulate(6, 0.1, 2), None);
+        assert_eq!(simulate(5, 0.0, 2), None);
+        assert_eq!(simulate(5, f64::NAN, 2), None);
+        assert_eq!(simulate(5, 0.1, 0), None);
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupError {
+    NotOrthonormal,
+    NotRightHanded,
+    InvalidBottomRow,
+}
+
+impl fmt::Display for GroupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupError::NotOrthonormal => write!(f, "rotation block is not orthonormal"),
+            GroupError::NotRightHanded => write!(f, "rotation block does not have determinant +1"),
+            GroupError::InvalidBottomRow => write!(f, "bottom row of transform is not [0 0 0 1]"),
+        }
+    }
+}
+
+impl std::error::Error for GroupError {}
+
+#[cfg(feature = "urdf")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrdfError {
+    Io(String),
+    Xml(String),
+    MissingAttribute { element: String, attribute: String },
+    InvalidNumber(String),
+    UnsupportedJoint(String),
+    UnknownLink(String),
+    InvalidInertia { link: String, error: InertiaError },
+}
+
+#[cfg(feature = "urdf")]
+impl fmt::Display for UrdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrdfError::Io(e) => write!(f, "could not read URDF file: {}", e),
+            UrdfError::Xml(e) => write!(f, "malformed URDF XML: {}", e),
+            UrdfError::MissingAttribute { element, attribute } => {
+                write!(f, "<{}> is missing attribute '{}'", element, attribute)
+            }
+            UrdfError::InvalidNumber(s) => write!(f, "invalid number list '{}'", s),
+            UrdfError::UnsupportedJoint(t) => write!(f, "unsupported joint type '{}'", t),
+            UrdfError::UnknownLink(l) => write!(f, "unknown link '{}'", l),
+            UrdfError::InvalidInertia { link, error } => {
+                write!(f, "link '{}' has an invalid inertia: {}", link, error)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "urdf")]
+impl std::error::Error for UrdfError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InertiaError {
+    NonPositiveMass,
+    NotSymmetric,
+    NotPositiveDefinite,
+    TriangleInequality,
+}
+
+impl fmt::Display for InertiaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InertiaError::NonPositiveMass => write!(f, "mass is not positive"),
+            InertiaError::NotSymmetric => write!(f, "spatial inertia is not symmetric"),
+            InertiaError::NotPositiveDefinite => {
+                write!(f, "rotational inertia is not positive definite")
+            }
+            InertiaError::TriangleInequality => {
+                write!(f, "principal moments violate the triangle inequality")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InertiaError {}
@@ -0,0 +1,234 @@
+extern crate nalgebra as na;
+use crate::groups::{MatrixExp, Se3Matrix, So3Skew, So3ToSe3, ToSkew, ToTwist, ToVec, Twist};
+use crate::interfaces::Numeric;
+use crate::transforms::axis_ang_3;
+use na::{ComplexField, Matrix3, Matrix4, Vector3, Vector6};
+use num::Zero;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Se3Bracket<T: Numeric<T>>(pub Matrix4<T>);
+
+pub trait ToBracket<T: Numeric<T>> {
+    fn to_bracket(&self) -> Se3Bracket<T>;
+}
+
+pub trait ToSkewV<T: Numeric<T>> {
+    fn to_skew_v(&self) -> (So3Skew<T>, Vector3<T>);
+}
+
+impl<T: Numeric<T>> ToBracket<T> for Matrix4<T> {
+    fn to_bracket(&self) -> Se3Bracket<T> {
+        Se3Bracket(*self)
+    }
+}
+
+impl<T: Numeric<T>> ToBracket<T> for Vector6<T> {
+    fn to_bracket(&self) -> Se3Bracket<T> {
+        Se3Bracket(Matrix4::new(
+            Zero::zero(),
+            -self[2],
+            self[1],
+            self[3],
+            self[2],
+            Zero::zero(),
+            -self[0],
+            self[4],
+            -self[1],
+            self[0],
+            Zero::zero(),
+            self[5],
+            Zero::zero(),
+            Zero::zero(),
+            Zero::zero(),
+            Zero::zero(),
+        ))
+    }
+}
+
+impl<T: Numeric<T>> ToBracket<T> for [T; 6] {
+    fn to_bracket(&self) -> Se3Bracket<T> {
+        Se3Bracket(Matrix4::new(
+            Zero::zero(),
+            -self[2],
+            self[1],
+            self[3],
+            self[2],
+            Zero::zero(),
+            -self[0],
+            self[4],
+            -self[1],
+            self[0],
+            Zero::zero(),
+            self[5],
+            Zero::zero(),
+            Zero::zero(),
+            Zero::zero(),
+            Zero::zero(),
+        ))
+    }
+}
+
+impl<T: Numeric<T>> ToSkewV<T> for Se3Bracket<T> {
+    fn to_skew_v(&self) -> (So3Skew<T>, Vector3<T>) {
+        (
+            So3Skew(self.0.fixed_view::<3, 3>(0, 0).clone_owned()),
+            self.0.fixed_view::<3, 1>(0, 3).clone_owned(),
+        )
+    }
+}
+
+impl<T: Numeric<T>> ToSkew<T> for Se3Bracket<T> {
+    fn to_skew(&self) -> So3Skew<T> {
+        self.to_skew_v().0
+    }
+}
+
+impl<T: Numeric<T>> ToVec<T> for Se3Bracket<T> {
+    type VecSize = Vector6<T>;
+
+    fn to_vec(&self) -> Vector6<T> {
+        Vector6::new(
+            self.0.m32, self.0.m13, self.0.m21, self.0.m14, self.0.m24, self.0.m34,
+        )
+    }
+}
+
+impl<T: Numeric<T>> ToTwist<T> for Se3Bracket<T> {
+    fn to_twist(&self) -> Twist<T> {
+        self.to_vec().to_twist()
+    }
+}
+
+impl<T: Numeric<T>> MatrixExp<T> for Se3Bracket<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    type Output = Se3Matrix<T>;
+
+    fn exp(&self) -> Se3Matrix<T> {
+        let (skew, v) = self.to_skew_v();
+        let theta = match axis_ang_3(skew.to_vec()) {
+            Some((_, theta)) => theta,
+            // Pure translation, the exponential is a translation by v
+            None => return skew.exp().to_se3(v),
+        };
+        let omgmat = skew.0 / theta;
+        let g = Matrix3::identity() * theta
+            + omgmat * (T::one() - theta.cos())
+            + omgmat * omgmat * (theta - theta.sin());
+        skew.exp().to_se3(g * v / theta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::{MatrixLog, ToSe3};
+    use crate::helpers::near_zero;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    #[test]
+    fn array_to_bracket() {
+        let test_mat = Matrix4::new(
+            0.0, -3.3, 2.2, 4.4, 3.3, 0.0, -1.1, 5.5, -2.2, 1.1, 0.0, 6.6, 0.0, 0.0, 0.0, 0.0,
+        );
+        let vec: [f64; 6] = [1.1, 2.2, 3.3, 4.4, 5.5, 6.6];
+        let res = vec.to_bracket();
+        assert_eq!(res.0, test_mat);
+    }
+
+    #[test]
+    fn vector6_to_bracket() {
+        let test_mat = Matrix4::new(0, -3, 2, 4, 3, 0, -1, 5, -2, 1, 0, 6, 0, 0, 0, 0);
+        let vec = Vector6::new(1, 2, 3, 4, 5, 6);
+        let res = vec.to_bracket();
+        assert_eq!(res.0, test_mat);
+    }
+
+    #[test]
+    fn matrix_to_bracket() {
+        let test_mat = Matrix4::new(
+            0.0, -3.0, 2.0, 4.0, 3.0, 0.0, -1.0, 5.0, -2.0, 1.0, 0.0, 6.0, 0.0, 0.0, 0.0, 0.0,
+        );
+        let res = test_mat.to_bracket();
+        assert_eq!(res.0, test_mat);
+    }
+
+    #[test]
+    fn bracket_to_skew_v() {
+        let bracket = [1, 2, 3, 4, 5, 6].to_bracket();
+        let (skew, v) = bracket.to_skew_v();
+        assert_eq!(skew.0, [1, 2, 3].to_skew().0);
+        assert_eq!(v, Vector3::new(4, 5, 6));
+    }
+
+    #[test]
+    fn bracket_to_vec() {
+        let bracket = Matrix4::new(0, -3, 2, 4, 3, 0, -1, 5, -2, 1, 0, 6, 0, 0, 0, 0).to_bracket();
+        let target_vec = Vector6::new(1, 2, 3, 4, 5, 6);
+        assert_eq!(bracket.to_vec(), target_vec);
+    }
+
+    #[test]
+    fn bracket_to_twist() {
+        let bracket = Matrix4::new(0, -3, 2, 4, 3, 0, -1, 5, -2, 1, 0, 6, 0, 0, 0, 0).to_bracket();
+        let target_twist = Vector6::new(1, 2, 3, 4, 5, 6).to_twist();
+        assert_eq!(bracket.to_twist().0, target_twist.0);
+    }
+
+    #[test]
+    fn bracket_exp() {
+        let bracket = [FRAC_PI_2, 0.0, 0.0, 0.0, 3.0 * FRAC_PI_4, 3.0 * FRAC_PI_4].to_bracket();
+        let tar = Matrix4::new(
+            1.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 1.0, 0.0, 3.0, 0.0, 0.0, 0.0, 1.0,
+        );
+        assert!(near_zero((bracket.exp().0 - tar).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn bracket_exp_translation() {
+        let bracket = [0.0, 0.0, 0.0, 1.0, 2.0, 3.0].to_bracket();
+        let tar = Matrix4::new(
+            1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 2.0, 0.0, 0.0, 1.0, 3.0, 0.0, 0.0, 0.0, 1.0,
+        );
+        assert_eq!(bracket.exp().0, tar);
+    }
+
+    #[test]
+    fn bracket_exp_log() {
+        let bracket = [0.2, -0.4, 0.7, 1.0, -2.0, 0.5].to_bracket();
+        let res = bracket.exp().log();
+        assert!(near_zero(
+            (res.to_vec() - bracket.to_vec()).norm(),
+            Some(1e-9)
+        ));
+    }
+
+    #[test]
+    fn bracket_exp_numeric() {
+        let bracket = [0.2, -0.4, 0.7, 1.0, -2.0, 0.5].to_bracket();
+        let res = bracket.0.exp();
+        assert!(near_zero((res - bracket.exp().0).norm(), Some(1e-9)));
+    }
+
+    #[cfg(feature = "expm")]
+    #[test]
+    fn bracket_exp_expm() {
+        let bracket = [0.2, -0.4, 0.7, 1.0, -2.0, 0.5].to_bracket();
+        let a = ndarray::Array2::from_shape_fn((4, 4), |(i, j)| bracket.0[(i, j)]);
+        let mut b = ndarray::Array2::zeros((4, 4));
+        expm::expm(&a, &mut b);
+        let res = Matrix4::from_fn(|i, j| b[(i, j)]);
+        assert!(near_zero((res - bracket.exp().0).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn bracket_exp_se3_roundtrip() {
+        let t = Matrix4::new(
+            1.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 1.0, 0.0, 3.0, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3();
+        assert!(near_zero((t.log().exp().0 - t.0).norm(), Some(1e-9)));
+    }
+}
@@ -0,0 +1,268 @@
+extern crate nalgebra as na;
+use crate::groups::{MatrixExp, So3Matrix, ToSkew, ToSo3};
+use crate::helpers::near_zero;
+use crate::interfaces::Numeric;
+use na::{RealField, Vector3};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EulerSequence {
+    Xyz,
+    Xzy,
+    Yxz,
+    Yzx,
+    Zxy,
+    Zyx,
+    Xyx,
+    Xzx,
+    Yxy,
+    Yzy,
+    Zxz,
+    Zyz,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EulerFrame {
+    // Each rotation is about an axis of the frame produced by the previous rotations
+    Intrinsic,
+    // Each rotation is about an axis of the fixed reference frame
+    Extrinsic,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EulerConvention {
+    pub sequence: EulerSequence,
+    pub frame: EulerFrame,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EulerAngles<T: Numeric<T>> {
+    pub angles: Vector3<T>,
+    pub convention: EulerConvention,
+}
+
+pub trait ToEuler<T: Numeric<T>> {
+    fn to_euler(&self, convention: EulerConvention) -> EulerAngles<T>;
+}
+
+impl EulerSequence {
+    fn axes(&self) -> [usize; 3] {
+        match self {
+            EulerSequence::Xyz => [0, 1, 2],
+            EulerSequence::Xzy => [0, 2, 1],
+            EulerSequence::Yxz => [1, 0, 2],
+            EulerSequence::Yzx => [1, 2, 0],
+            EulerSequence::Zxy => [2, 0, 1],
+            EulerSequence::Zyx => [2, 1, 0],
+            EulerSequence::Xyx => [0, 1, 0],
+            EulerSequence::Xzx => [0, 2, 0],
+            EulerSequence::Yxy => [1, 0, 1],
+            EulerSequence::Yzy => [1, 2, 1],
+            EulerSequence::Zxz => [2, 0, 2],
+            EulerSequence::Zyz => [2, 1, 2],
+        }
+    }
+}
+
+impl EulerConvention {
+    // Roll about the fixed x axis, then pitch about fixed y, then yaw about fixed z
+    pub const RPY: EulerConvention = EulerConvention {
+        sequence: EulerSequence::Xyz,
+        frame: EulerFrame::Extrinsic,
+    };
+
+    pub fn new(sequence: EulerSequence, frame: EulerFrame) -> EulerConvention {
+        EulerConvention { sequence, frame }
+    }
+
+    // The axes in the order the matrices are multiplied, R = R_a(t_a) R_b(t_b) R_c(t_c)
+    fn intrinsic_axes(&self) -> [usize; 3] {
+        let mut axes = self.sequence.axes();
+        if self.frame == EulerFrame::Extrinsic {
+            axes.reverse();
+        }
+        axes
+    }
+}
+
+impl<T: Numeric<T> + RealField> EulerAngles<T>
+where
+    f64: From<T>,
+{
+    pub fn new(angles: Vector3<T>, convention: EulerConvention) -> EulerAngles<T> {
+        EulerAngles { angles, convention }
+    }
+
+    pub fn from_rpy(roll: T, pitch: T, yaw: T) -> EulerAngles<T> {
+        EulerAngles::new(Vector3::new(roll, pitch, yaw), EulerConvention::RPY)
+    }
+
+    pub fn rpy(&self) -> (T, T, T) {
+        let angles = self.to_convention(EulerConvention::RPY).angles;
+        (angles[0], angles[1], angles[2])
+    }
+
+    pub fn to_convention(&self, convention: EulerConvention) -> EulerAngles<T> {
+        if convention == self.convention {
+            return *self;
+        }
+        self.to_so3().to_euler(convention)
+    }
+}
+
+impl<T: Numeric<T> + RealField> ToSo3<T> for EulerAngles<T>
+where
+    f64: From<T>,
+{
+    fn to_so3(&self) -> So3Matrix<T> {
+        let axes = self.convention.sequence.axes();
+        let [r1, r2, r3] = [0, 1, 2].map(|i| {
+            (Vector3::ith(axes[i], T::one()) * self.angles[i])
+                .to_skew()
+                .exp()
+        });
+        match self.convention.frame {
+            EulerFrame::Intrinsic => r1 * r2 * r3,
+            EulerFrame::Extrinsic => r3 * r2 * r1,
+        }
+    }
+}
+
+impl<T: Numeric<T> + RealField> ToEuler<T> for So3Matrix<T>
+where
+    f64: From<T>,
+{
+    fn to_euler(&self, convention: EulerConvention) -> EulerAngles<T> {
+        let r = &self.0;
+        let [i, j, k] = convention.intrinsic_axes();
+        // The axis not used by the middle rotation or the outer ones
+        let m = 3 - i - j;
+        let sign = if (j + 3 - i) % 3 == 1 {
+            T::one()
+        } else {
+            -T::one()
+        };
+        let (a, b, c);
+        if i != k {
+            // Tait-Bryan angles, the middle angle is in [-pi/2, pi/2]
+            let cos_b = (r[(i, i)] * r[(i, i)] + r[(i, j)] * r[(i, j)]).sqrt();
+            b = (sign * r[(i, k)]).atan2(cos_b);
+            if near_zero(cos_b, None) {
+                // Gimbal lock, only a + c or a - c is defined so c is set to zero
+                a = (sign * r[(k, j)]).atan2(r[(j, j)]);
+                c = T::zero();
+            } else {
+                a = (-sign * r[(j, k)]).atan2(r[(k, k)]);
+                c = (-sign * r[(i, j)]).atan2(r[(i, i)]);
+            }
+        } else {
+            // Proper Euler angles, the middle angle is in [0, pi]
+            let sin_b = (r[(i, j)] * r[(i, j)] + r[(i, m)] * r[(i, m)]).sqrt();
+            b = sin_b.atan2(r[(i, i)]);
+            if near_zero(sin_b, None) {
+                a = (sign * r[(m, j)]).atan2(r[(j, j)]);
+                c = T::zero();
+            } else {
+                a = r[(j, i)].atan2(-sign * r[(m, i)]);
+                c = r[(i, j)].atan2(sign * r[(i, m)]);
+            }
+        }
+        let angles = match convention.frame {
+            EulerFrame::Intrinsic => Vector3::new(a, b, c),
+            EulerFrame::Extrinsic => Vector3::new(c, b, a),
+        };
+        EulerAngles::new(angles, convention)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use na::Matrix3;
+    use std::f64::consts::FRAC_PI_2;
+
+    const SEQUENCES: [EulerSequence; 12] = [
+        EulerSequence::Xyz,
+        EulerSequence::Xzy,
+        EulerSequence::Yxz,
+        EulerSequence::Yzx,
+        EulerSequence::Zxy,
+        EulerSequence::Zyx,
+        EulerSequence::Xyx,
+        EulerSequence::Xzx,
+        EulerSequence::Yxy,
+        EulerSequence::Yzy,
+        EulerSequence::Zxz,
+        EulerSequence::Zyz,
+    ];
+
+    #[test]
+    fn euler_zyx_intrinsic() {
+        let convention = EulerConvention::new(EulerSequence::Zyx, EulerFrame::Intrinsic);
+        let r = EulerAngles::new(Vector3::new(FRAC_PI_2, 0.0, 0.0), convention).to_so3();
+        let tar = Matrix3::new(0.0, -1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+        assert!(near_zero((r.0 - tar).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn euler_roundtrip_all_conventions() {
+        for sequence in SEQUENCES {
+            for frame in [EulerFrame::Intrinsic, EulerFrame::Extrinsic] {
+                let convention = EulerConvention::new(sequence, frame);
+                let angles = if sequence.axes()[0] == sequence.axes()[2] {
+                    Vector3::new(0.3, 1.1, -0.7)
+                } else {
+                    Vector3::new(0.3, -0.4, -0.7)
+                };
+                let res = EulerAngles::new(angles, convention)
+                    .to_so3()
+                    .to_euler(convention);
+                assert_eq!(res.convention, convention);
+                assert!(near_zero((res.angles - angles).norm(), Some(1e-9)));
+            }
+        }
+    }
+
+    #[test]
+    fn euler_extrinsic_is_reversed_intrinsic() {
+        let extrinsic = EulerConvention::new(EulerSequence::Xyz, EulerFrame::Extrinsic);
+        let intrinsic = EulerConvention::new(EulerSequence::Zyx, EulerFrame::Intrinsic);
+        let r = EulerAngles::new(Vector3::new(0.1, 0.2, 0.3), extrinsic).to_so3();
+        let res = r.to_euler(intrinsic);
+        assert!(near_zero(
+            (res.angles - Vector3::new(0.3, 0.2, 0.1)).norm(),
+            Some(1e-9)
+        ));
+    }
+
+    #[test]
+    fn euler_gimbal_lock() {
+        for sequence in SEQUENCES {
+            for frame in [EulerFrame::Intrinsic, EulerFrame::Extrinsic] {
+                let convention = EulerConvention::new(sequence, frame);
+                let middle = if sequence.axes()[0] == sequence.axes()[2] {
+                    0.0
+                } else {
+                    FRAC_PI_2
+                };
+                let r = EulerAngles::new(Vector3::new(0.4, middle, 0.2), convention).to_so3();
+                let res = r.to_euler(convention);
+                assert!(res.angles.iter().all(|a| a.is_finite()));
+                assert!(near_zero((res.to_so3().0 - r.0).norm(), Some(1e-9)));
+            }
+        }
+    }
+
+    #[test]
+    fn rpy() {
+        let angles = EulerAngles::from_rpy(0.1, -0.2, 0.3);
+        let (roll, pitch, yaw) = angles
+            .to_convention(EulerConvention::new(
+                EulerSequence::Zyx,
+                EulerFrame::Intrinsic,
+            ))
+            .rpy();
+        assert!(near_zero(roll - 0.1, Some(1e-9)));
+        assert!(near_zero(pitch + 0.2, Some(1e-9)));
+        assert!(near_zero(yaw - 0.3, Some(1e-9)));
+    }
+}
@@ -1,9 +1,21 @@
+mod bracket;
+mod euler;
+mod quaternion;
+mod screw;
 mod se3;
 mod shared_traits;
+mod skew;
 mod so3;
 mod twist;
+mod wrench;
 
-pub use se3::{Se3Matrix, ToSe3};
-pub use shared_traits::ToVec;
-pub use so3::{So3Matrix, ToSo3};
-pub use twist::{ToTwist, Twist};
+pub use bracket::{Se3Bracket, ToBracket, ToSkewV};
+pub use euler::{EulerAngles, EulerConvention, EulerFrame, EulerSequence, ToEuler};
+pub use quaternion::{QuatExp, ToIsometry3, ToRotation3, ToUnitQuaternion};
+pub use screw::{ScrewAxis, ToScrewAxis};
+pub use se3::{Adjoint, Se3Distance, Se3Matrix, ToRP, ToSe3, TryToSe3};
+pub use shared_traits::{GroupAction, Inverse, MatrixExp, MatrixLog, ToVec};
+pub use skew::{So3Skew, ToSkew};
+pub use so3::{So3Distance, So3Matrix, So3ToSe3, ToSo3, TryToSo3};
+pub use twist::{Ad, ToTwist, Twist};
+pub use wrench::{CoAd, Power, ToWrench, Wrench};
@@ -0,0 +1,177 @@
+extern crate nalgebra as na;
+use crate::groups::{
+    MatrixLog, Se3Matrix, So3Matrix, So3Skew, So3ToSe3, ToRP, ToSe3, ToSkew, ToSo3, ToVec,
+};
+use crate::interfaces::Numeric;
+use crate::transforms::axis_ang_3;
+use na::{Isometry3, Quaternion, RealField, Rotation3, Translation3, UnitQuaternion, Vector3};
+
+pub trait ToUnitQuaternion<T: Numeric<T>> {
+    fn to_unit_quaternion(&self) -> UnitQuaternion<T>;
+}
+
+pub trait ToRotation3<T: Numeric<T>> {
+    fn to_rotation3(&self) -> Rotation3<T>;
+}
+
+pub trait ToIsometry3<T: Numeric<T>> {
+    fn to_isometry3(&self) -> Isometry3<T>;
+}
+
+pub trait QuatExp<T: Numeric<T>> {
+    fn quat_exp(&self) -> UnitQuaternion<T>;
+}
+
+impl<T: Numeric<T> + RealField> ToUnitQuaternion<T> for So3Matrix<T> {
+    fn to_unit_quaternion(&self) -> UnitQuaternion<T> {
+        UnitQuaternion::from_rotation_matrix(&self.to_rotation3())
+    }
+}
+
+impl<T: Numeric<T> + RealField> ToRotation3<T> for So3Matrix<T> {
+    fn to_rotation3(&self) -> Rotation3<T> {
+        Rotation3::from_matrix_unchecked(self.0)
+    }
+}
+
+impl<T: Numeric<T> + RealField> ToSo3<T> for UnitQuaternion<T> {
+    fn to_so3(&self) -> So3Matrix<T> {
+        So3Matrix(self.to_rotation_matrix().into_inner())
+    }
+}
+
+impl<T: Numeric<T> + RealField> ToSo3<T> for Rotation3<T> {
+    fn to_so3(&self) -> So3Matrix<T> {
+        So3Matrix(*self.matrix())
+    }
+}
+
+impl<T: Numeric<T> + RealField> ToIsometry3<T> for Se3Matrix<T> {
+    fn to_isometry3(&self) -> Isometry3<T> {
+        let (r, p) = self.to_rp();
+        Isometry3::from_parts(Translation3::from(p), r.to_unit_quaternion())
+    }
+}
+
+impl<T: Numeric<T> + RealField> ToSe3<T> for Isometry3<T> {
+    fn to_se3(&self) -> Se3Matrix<T> {
+        self.rotation.to_so3().to_se3(self.translation.vector)
+    }
+}
+
+impl<T: Numeric<T> + RealField> QuatExp<T> for Vector3<T>
+where
+    f64: From<T>,
+{
+    fn quat_exp(&self) -> UnitQuaternion<T> {
+        match axis_ang_3(*self) {
+            Some((omg, theta)) => {
+                let half_theta = theta / (T::one() + T::one());
+                let v = omg * half_theta.sin();
+                UnitQuaternion::new_unchecked(Quaternion::new(half_theta.cos(), v[0], v[1], v[2]))
+            }
+            None => UnitQuaternion::identity(),
+        }
+    }
+}
+
+impl<T: Numeric<T> + RealField> QuatExp<T> for So3Skew<T>
+where
+    f64: From<T>,
+{
+    fn quat_exp(&self) -> UnitQuaternion<T> {
+        self.to_vec().quat_exp()
+    }
+}
+
+impl<T: Numeric<T> + RealField> MatrixLog<T> for UnitQuaternion<T>
+where
+    f64: From<T>,
+{
+    type Output = So3Skew<T>;
+
+    fn log(&self) -> So3Skew<T> {
+        // q and -q are the same rotation, pick the one giving theta in [0, pi]
+        let q = if self.w < T::zero() {
+            -self.into_inner()
+        } else {
+            self.into_inner()
+        };
+        match axis_ang_3(q.imag()) {
+            Some((omg, sin_half_theta)) => {
+                let theta = (T::one() + T::one()) * sin_half_theta.atan2(q.w);
+                (omg * theta).to_skew()
+            }
+            None => So3Skew(na::Matrix3::zeros()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::{MatrixExp, ToBracket};
+    use crate::helpers::near_zero;
+    use na::Matrix3;
+
+    #[test]
+    fn so3_quaternion_roundtrip() {
+        let r = [0.3, -0.2, 0.9].to_skew().exp();
+        let q = r.to_unit_quaternion();
+        assert!(near_zero((q.to_so3().0 - r.0).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn quaternion_matches_nalgebra() {
+        let r = Matrix3::new(0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0).to_so3();
+        let q = r.to_unit_quaternion();
+        let axis = Vector3::new(1.0, 1.0, 1.0).normalize();
+        let tar = UnitQuaternion::from_scaled_axis(axis * 2.0 * std::f64::consts::FRAC_PI_3);
+        assert!(near_zero(q.angle_to(&tar), Some(1e-9)));
+    }
+
+    #[test]
+    fn so3_rotation3_roundtrip() {
+        let r = [0.3, -0.2, 0.9].to_skew().exp();
+        assert_eq!(r.to_rotation3().to_so3().0, r.0);
+    }
+
+    #[test]
+    fn se3_isometry_roundtrip() {
+        let t = [0.2, -0.4, 0.7, 1.0, -2.0, 0.5].to_bracket().exp();
+        let iso = t.to_isometry3();
+        assert!(near_zero((iso.to_homogeneous() - t.0).norm(), Some(1e-9)));
+        assert!(near_zero((iso.to_se3().0 - t.0).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn quaternion_exp_matches_so3_exp() {
+        let omg = Vector3::new(0.3, -0.2, 0.9);
+        let q = omg.quat_exp();
+        let r = omg.to_skew().exp();
+        assert!(near_zero((q.to_so3().0 - r.0).norm(), Some(1e-9)));
+        assert_eq!(
+            Vector3::new(0.0, 0.0, 0.0).quat_exp(),
+            UnitQuaternion::identity()
+        );
+    }
+
+    #[test]
+    fn quaternion_log_matches_so3_log() {
+        let r = Matrix3::new(0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0).to_so3();
+        let q = r.to_unit_quaternion();
+        assert!(near_zero((q.log().0 - r.log().0).norm(), Some(1e-9)));
+        let flipped = UnitQuaternion::new_unchecked(-q.into_inner());
+        assert!(near_zero((flipped.log().0 - r.log().0).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn quaternion_exp_log() {
+        let skew = [0.3, -0.2, 0.9].to_skew();
+        assert!(near_zero(
+            (skew.quat_exp().log().0 - skew.0).norm(),
+            Some(1e-9)
+        ));
+        assert_eq!(UnitQuaternion::<f64>::identity().log().0, Matrix3::zeros());
+    }
+}
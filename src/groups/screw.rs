@@ -0,0 +1,143 @@
+extern crate nalgebra as na;
+use crate::groups::{ToTwist, Twist};
+use crate::helpers::near_zero;
+use crate::interfaces::Numeric;
+use crate::transforms::axis_ang_6;
+use na::{ClosedMul, ComplexField, Point3, Vector3, Vector6};
+use std::ops::Mul;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrewAxis<T: Numeric<T>>(pub Vector6<T>);
+
+pub trait ToScrewAxis<T: Numeric<T>> {
+    fn to_screw_axis(&self) -> Option<(ScrewAxis<T>, T)>;
+}
+
+impl<T: Numeric<T>> ScrewAxis<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    // s is expected to be a unit vector, h is the pitch (linear over angular speed)
+    pub fn from_qsh(q: &Point3<T>, s: &Vector3<T>, h: T) -> ScrewAxis<T> {
+        let v = -s.cross(&q.coords) + s * h;
+        ScrewAxis(Vector6::new(s[0], s[1], s[2], v[0], v[1], v[2]))
+    }
+
+    pub fn normalize(&self) -> Option<ScrewAxis<T>> {
+        axis_ang_6(self.0).map(|(s, _)| ScrewAxis(s))
+    }
+
+    pub fn magnitude(&self) -> T {
+        axis_ang_6(self.0).map_or(T::zero(), |(_, theta)| theta)
+    }
+
+    pub fn pitch(&self) -> Option<T> {
+        let omg = self.0.fixed_view::<3, 1>(0, 0);
+        let v = self.0.fixed_view::<3, 1>(3, 0);
+        let omg_sq = omg.norm_squared();
+        if near_zero(omg_sq, None) {
+            // Pure translation, the pitch is infinite
+            return None;
+        }
+        Some(omg.dot(&v) / omg_sq)
+    }
+
+    pub fn axis(&self) -> Option<(Point3<T>, Vector3<T>)> {
+        let omg = self.0.fixed_view::<3, 1>(0, 0).clone_owned();
+        let v = self.0.fixed_view::<3, 1>(3, 0).clone_owned();
+        let omg_sq = omg.norm_squared();
+        if near_zero(omg_sq, None) {
+            return None;
+        }
+        // The point on the axis closest to the origin
+        let q = omg.cross(&v) / omg_sq;
+        Some((Point3::from(q), omg / omg_sq.sqrt()))
+    }
+}
+
+impl<T: Numeric<T>> ToScrewAxis<T> for Vector6<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    fn to_screw_axis(&self) -> Option<(ScrewAxis<T>, T)> {
+        axis_ang_6(*self).map(|(s, theta)| (ScrewAxis(s), theta))
+    }
+}
+
+impl<T: Numeric<T>> ToScrewAxis<T> for Twist<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    fn to_screw_axis(&self) -> Option<(ScrewAxis<T>, T)> {
+        self.0.to_screw_axis()
+    }
+}
+
+impl<T: Numeric<T>> ToTwist<T> for ScrewAxis<T> {
+    fn to_twist(&self) -> Twist<T> {
+        Twist(self.0)
+    }
+}
+
+impl<T: Numeric<T> + ClosedMul> Mul<T> for ScrewAxis<T> {
+    type Output = Twist<T>;
+
+    fn mul(self, rhs: T) -> Twist<T> {
+        Twist(self.0 * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn screw_from_qsh() {
+        let q = Point3::new(3.0, 0.0, 0.0);
+        let s = Vector3::new(0.0, 0.0, 1.0);
+        let res = ScrewAxis::from_qsh(&q, &s, 2.0);
+        assert_eq!(res.0, Vector6::new(0.0, 0.0, 1.0, 0.0, -3.0, 2.0));
+    }
+
+    #[test]
+    fn screw_pitch_axis() {
+        let q = Point3::new(3.0, 0.0, 0.0);
+        let s = Vector3::new(0.0, 0.0, 1.0);
+        let screw = ScrewAxis::from_qsh(&q, &s, 2.0);
+        assert!(near_zero((screw.pitch().unwrap() - 2.0).abs(), Some(1e-9)));
+        let (point, dir) = screw.axis().unwrap();
+        assert!(near_zero((point - q).norm(), Some(1e-9)));
+        assert_eq!(dir, s);
+    }
+
+    #[test]
+    fn screw_pure_translation() {
+        let screw = ScrewAxis(Vector6::new(0.0, 0.0, 0.0, 0.0, 3.0, 4.0));
+        assert_eq!(screw.pitch(), None);
+        assert_eq!(screw.axis(), None);
+        assert!(near_zero((screw.magnitude() - 5.0).abs(), Some(1e-9)));
+        assert_eq!(
+            screw.normalize().unwrap().0,
+            Vector6::new(0.0, 0.0, 0.0, 0.0, 0.6, 0.8)
+        );
+    }
+
+    #[test]
+    fn twist_to_screw_axis() {
+        let twist = [0.0, 0.0, 2.0, 0.0, -6.0, 4.0].to_twist();
+        let (screw, theta_dot) = twist.to_screw_axis().unwrap();
+        assert_eq!(screw.0, Vector6::new(0.0, 0.0, 1.0, 0.0, -3.0, 2.0));
+        assert_eq!(theta_dot, 2.0);
+        assert_eq!((screw * theta_dot).0, twist.0);
+        assert_eq!([0.0; 6].to_twist().to_screw_axis(), None);
+    }
+
+    #[test]
+    fn screw_to_twist() {
+        let screw = ScrewAxis(Vector6::new(0.0, 0.0, 1.0, 0.0, -3.0, 2.0));
+        assert_eq!(screw.to_twist().0, screw.0);
+    }
+}
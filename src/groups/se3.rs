@@ -1,68 +1,114 @@
 extern crate nalgebra as na;
-use crate::groups::{So3Matrix, ToSo3, ToTwist, ToVec, Twist};
+use crate::concat::{allocate_block_output, Block, HCat, VCat};
+use crate::errors::GroupError;
+use crate::groups::{
+    GroupAction, Inverse, MatrixLog, Se3Bracket, So3Distance, So3Matrix, So3ToSe3, ToSkew, ToSo3,
+    ToTwist, ToVec, ToWrench, TryToSo3, Twist, Wrench,
+};
+use crate::helpers::near_zero;
 use crate::interfaces::Numeric;
-use na::{Matrix4, Vector3, Vector6};
-use num::{One, Zero};
+use crate::transforms::axis_ang_3;
+use na::{
+    ClosedAdd, ClosedMul, ComplexField, Matrix3, Matrix4, Matrix6, Point3, RowVector4, Vector3,
+    Vector4,
+};
+use std::ops::Mul;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Se3Matrix<T: Numeric<T>>(pub Matrix4<T>);
 
 pub trait ToSe3<T: Numeric<T>> {
     fn to_se3(&self) -> Se3Matrix<T>;
 }
 
+pub trait TryToSe3<T: Numeric<T>> {
+    fn try_to_se3(&self) -> Result<Se3Matrix<T>, GroupError>;
+}
+
 pub trait ToRP<T: Numeric<T>> {
     fn to_rp(&self) -> (So3Matrix<T>, Vector3<T>);
 }
 
+pub trait Adjoint<T: Numeric<T>> {
+    fn adjoint(&self) -> Matrix6<T>;
+    fn adjoint_inv(&self) -> Matrix6<T>;
+    fn coadjoint(&self) -> Matrix6<T>;
+    fn transform_twist(&self, twist: &Twist<T>) -> Twist<T>;
+    fn transform_wrench(&self, wrench: &Wrench<T>) -> Wrench<T>;
+}
+
+pub trait Se3Distance<T: Numeric<T>> {
+    fn distance_to_se3(&self) -> T;
+    fn is_se3(&self) -> bool;
+    fn project_to_se3(&self) -> Se3Matrix<T>;
+}
+
 impl<T: Numeric<T>> ToSe3<T> for Matrix4<T> {
     fn to_se3(&self) -> Se3Matrix<T> {
         Se3Matrix(*self)
     }
 }
 
-impl<T: Numeric<T>> ToSe3<T> for Vector6<T> {
-    fn to_se3(&self) -> Se3Matrix<T> {
-        Se3Matrix(Matrix4::new(
-            Zero::zero(),
-            -self[2],
-            self[1],
-            self[3],
-            self[2],
-            Zero::zero(),
-            -self[0],
-            self[4],
-            -self[1],
-            self[0],
-            Zero::zero(),
-            self[5],
-            Zero::zero(),
-            Zero::zero(),
-            Zero::zero(),
-            One::one(),
-        ))
-    }
-}
-
-impl<T: Numeric<T>> ToSe3<T> for [T; 6] {
-    fn to_se3(&self) -> Se3Matrix<T> {
-        Se3Matrix(Matrix4::new(
-            Zero::zero(),
-            -self[2],
-            self[1],
-            self[3],
-            self[2],
-            Zero::zero(),
-            -self[0],
-            self[4],
-            -self[1],
-            self[0],
-            Zero::zero(),
-            self[5],
-            Zero::zero(),
-            Zero::zero(),
-            Zero::zero(),
-            One::one(),
-        ))
+impl<T: Numeric<T>> TryToSe3<T> for Matrix4<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    fn try_to_se3(&self) -> Result<Se3Matrix<T>, GroupError> {
+        let r = self.fixed_view::<3, 3>(0, 0).clone_owned().try_to_so3()?;
+        let bottom = self.fixed_view::<1, 4>(3, 0)
+            - RowVector4::new(T::zero(), T::zero(), T::zero(), T::one());
+        if !near_zero(bottom.norm(), Some(na::convert(1e-3))) {
+            return Err(GroupError::InvalidBottomRow);
+        }
+        Ok(r.to_se3(self.fixed_view::<3, 1>(0, 3).clone_owned()))
+    }
+}
+
+impl<T: Numeric<T>> Se3Distance<T> for Matrix4<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    fn distance_to_se3(&self) -> T {
+        let r = self.fixed_view::<3, 3>(0, 0);
+        if r.determinant() > T::zero() {
+            let mut mat_logic = *self;
+            mat_logic
+                .fixed_view_mut::<3, 3>(0, 0)
+                .copy_from(&(r.transpose() * r));
+            mat_logic.fixed_view_mut::<3, 1>(0, 3).fill(T::zero());
+            (mat_logic - Matrix4::identity()).norm()
+        } else {
+            na::convert(1e9)
+        }
+    }
+
+    fn is_se3(&self) -> bool {
+        near_zero(self.distance_to_se3(), Some(na::convert(1e-3)))
+    }
+
+    fn project_to_se3(&self) -> Se3Matrix<T> {
+        self.to_se3().project_to_se3()
+    }
+}
+
+impl<T: Numeric<T>> Se3Distance<T> for Se3Matrix<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    fn distance_to_se3(&self) -> T {
+        self.0.distance_to_se3()
+    }
+
+    fn is_se3(&self) -> bool {
+        self.0.is_se3()
+    }
+
+    fn project_to_se3(&self) -> Se3Matrix<T> {
+        let (r, p) = self.to_rp();
+        r.project_to_so3().to_se3(p)
     }
 }
 
@@ -81,53 +127,161 @@ impl<T: Numeric<T>> ToSo3<T> for Se3Matrix<T> {
     }
 }
 
-impl<T: Numeric<T>> ToVec<T> for Se3Matrix<T> {
-    type VecSize = Vector6<T>;
+impl<T: Numeric<T> + ClosedAdd + ClosedMul> Inverse<T> for Se3Matrix<T> {
+    type Output = Se3Matrix<T>;
 
-    fn to_vec(&self) -> Vector6<T> {
-        Vector6::new(
-            self.0.m32, self.0.m13, self.0.m21, self.0.m14, self.0.m24, self.0.m34,
-        )
+    fn inv(&self) -> Se3Matrix<T> {
+        let (r, p) = self.to_rp();
+        let r_t = r.inv();
+        let p_inv = -(r_t.0 * p);
+        r_t.to_se3(p_inv)
     }
 }
 
-impl<T: Numeric<T>> ToTwist<T> for Se3Matrix<T> {
-    fn to_twist(&self) -> Twist<T> {
-        self.to_vec().to_twist()
+impl<T: Numeric<T> + ClosedAdd + ClosedMul> Mul for Se3Matrix<T> {
+    type Output = Se3Matrix<T>;
+
+    fn mul(self, rhs: Se3Matrix<T>) -> Se3Matrix<T> {
+        Se3Matrix(self.0 * rhs.0)
+    }
+}
+
+impl<T: Numeric<T> + ClosedAdd + ClosedMul> GroupAction<T> for Se3Matrix<T> {
+    fn transform_point(&self, point: &Point3<T>) -> Point3<T> {
+        let (r, p) = self.to_rp();
+        r.transform_point(point) + p
+    }
+
+    fn transform_vector(&self, vector: &Vector3<T>) -> Vector3<T> {
+        self.to_so3().transform_vector(vector)
+    }
+
+    fn transform_homogeneous(&self, vector: &Vector4<T>) -> Vector4<T> {
+        self.0 * vector
+    }
+}
+
+impl<T: Numeric<T> + ClosedAdd + ClosedMul> Adjoint<T> for Se3Matrix<T> {
+    fn adjoint(&self) -> Matrix6<T> {
+        let (r, p) = self.to_rp();
+        let zeros = Matrix3::zeros();
+        bcat![r.0, zeros;
+              p.to_skew().0 * r.0, r.0]
+    }
+
+    fn adjoint_inv(&self) -> Matrix6<T> {
+        self.inv().adjoint()
+    }
+
+    fn coadjoint(&self) -> Matrix6<T> {
+        self.adjoint().transpose()
+    }
+
+    fn transform_twist(&self, twist: &Twist<T>) -> Twist<T> {
+        (self.adjoint() * twist.0).to_twist()
+    }
+
+    fn transform_wrench(&self, wrench: &Wrench<T>) -> Wrench<T> {
+        // F_a = [Ad_{T_ba}]^T F_b with T_ba the inverse of self
+        (self.inv().coadjoint() * wrench.0).to_wrench()
+    }
+}
+
+impl<T: Numeric<T>> MatrixLog<T> for Se3Matrix<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    type Output = Se3Bracket<T>;
+
+    fn log(&self) -> Se3Bracket<T> {
+        let (r, p) = self.to_rp();
+        let omgmat = r.log();
+        let v = if let Some((_, theta)) = axis_ang_3(omgmat.to_vec()) {
+            let two = T::one() + T::one();
+            let half_theta = theta / two;
+            let g_inv = Matrix3::identity() - omgmat.0 / two
+                + omgmat.0
+                    * omgmat.0
+                    * ((T::one() / theta - T::one() / half_theta.tan() / two) / theta);
+            g_inv * p
+        } else {
+            p
+        };
+        Se3Bracket(bcat![omgmat.0, v;
+                         RowVector4::zeros()])
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::groups::ToSo3;
-    use na::Matrix3;
+    use crate::groups::{MatrixExp, Power, ToBracket};
+    use na::Vector6;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
 
     #[test]
-    fn array_to_se3() {
+    fn matrix_to_se3() {
         let test_mat = Matrix4::new(
-            0.0, -3.3, 2.2, 4.4, 3.3, 0.0, -1.1, 5.5, -2.2, 1.1, 0.0, 6.6, 0.0, 0.0, 0.0, 1.0,
+            1.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 1.0, 0.0, 3.0, 0.0, 0.0, 0.0, 1.0,
         );
-        let vec: [f64; 6] = [1.1, 2.2, 3.3, 4.4, 5.5, 6.6];
-        let res = vec.to_se3();
+        let res = test_mat.to_se3();
         assert_eq!(res.0, test_mat);
     }
 
     #[test]
-    fn vector6_to_se3() {
-        let test_mat = Matrix4::new(0, -3, 2, 4, 3, 0, -1, 5, -2, 1, 0, 6, 0, 0, 0, 1);
-        let vec = Vector6::new(1, 2, 3, 4, 5, 6);
-        let res = vec.to_se3();
-        assert_eq!(res.0, test_mat);
+    fn matrix_try_to_se3() {
+        let t = Matrix4::new(
+            1.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 1.0, 0.0, 3.0, 0.0, 0.0, 0.0, 1.0,
+        );
+        assert_eq!(t.try_to_se3().unwrap().0, t);
+        let mut bad_row = t;
+        bad_row.m43 = 0.1;
+        assert_eq!(
+            bad_row.try_to_se3().err(),
+            Some(GroupError::InvalidBottomRow)
+        );
+        let mut bad_rot = t;
+        bad_rot.m22 = 0.1;
+        assert_eq!(bad_rot.try_to_se3().err(), Some(GroupError::NotOrthonormal));
     }
 
     #[test]
-    fn matrix_to_se3() {
-        let test_mat = Matrix4::new(
-            0.0, -3.0, 2.0, 4.0, 3.0, 0.0, -1.0, 5.0, -2.0, 1.0, 0.0, 6.0, 0.0, 0.0, 0.0, 1.0,
+    fn distance_to_se3() {
+        let mat = Matrix4::new(
+            1.0, 0.0, 0.0, 1.2, 0.0, 0.1, -0.95, 1.5, 0.0, 1.0, 0.1, -0.9, 0.0, 0.0, 0.1, 0.98,
         );
-        let res = test_mat.to_se3();
-        assert_eq!(res.0, test_mat);
+        assert!(near_zero(mat.distance_to_se3() - 0.134931, Some(1e-6)));
+        assert!(!mat.is_se3());
+    }
+
+    #[test]
+    fn project_to_se3() {
+        let mat = Matrix4::new(
+            0.675, 0.150, 0.720, 1.2, 0.370, 0.771, -0.511, 5.4, -0.630, 0.619, 0.472, 3.6, 0.003,
+            0.002, 0.010, 0.9,
+        );
+        let tar = Matrix4::new(
+            0.67901136,
+            0.14894516,
+            0.71885945,
+            1.2,
+            0.37320708,
+            0.77319584,
+            -0.51272279,
+            5.4,
+            -0.63218672,
+            0.61642804,
+            0.46942137,
+            3.6,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        );
+        let res = mat.project_to_se3();
+        assert!(near_zero((res.0 - tar).norm(), Some(1e-6)));
+        assert!(res.is_se3());
     }
 
     #[test]
@@ -155,16 +309,96 @@ mod tests {
     }
 
     #[test]
-    fn se3_to_vec() {
-        let se3_mat = Matrix4::new(0, -3, 2, 4, 3, 0, -1, 5, -2, 1, 0, 6, 0, 0, 0, 1).to_se3();
-        let target_vec = Vector6::new(1, 2, 3, 4, 5, 6);
-        assert_eq!(se3_mat.to_vec(), target_vec);
+    fn se3_inv() {
+        let t = Matrix4::new(1, 0, 0, 0, 0, 0, -1, 0, 0, 1, 0, 3, 0, 0, 0, 1).to_se3();
+        let tar = Matrix4::new(1, 0, 0, 0, 0, 0, 1, -3, 0, -1, 0, 0, 0, 0, 0, 1);
+        assert_eq!(t.inv().0, tar);
+        assert_eq!((t * t.inv()).0, Matrix4::identity());
+    }
+
+    #[test]
+    fn se3_mul() {
+        let t1 = Matrix4::new(1, 0, 0, 0, 0, 0, -1, 0, 0, 1, 0, 3, 0, 0, 0, 1).to_se3();
+        let t2 = Matrix4::new(1, 0, 0, 1, 0, 1, 0, 2, 0, 0, 1, 0, 0, 0, 0, 1).to_se3();
+        let tar = Matrix4::new(1, 0, 0, 1, 0, 0, -1, 0, 0, 1, 0, 5, 0, 0, 0, 1);
+        assert_eq!((t1 * t2).0, tar);
+    }
+
+    #[test]
+    fn se3_action() {
+        let t = Matrix4::new(1, 0, 0, 0, 0, 0, -1, 0, 0, 1, 0, 3, 0, 0, 0, 1).to_se3();
+        assert_eq!(
+            t.transform_point(&Point3::new(1, 2, 3)),
+            Point3::new(1, -3, 5)
+        );
+        assert_eq!(
+            t.transform_vector(&Vector3::new(1, 2, 3)),
+            Vector3::new(1, -3, 2)
+        );
+        assert_eq!(
+            t.transform_homogeneous(&Vector4::new(1, 2, 3, 1)),
+            Vector4::new(1, -3, 5, 1)
+        );
+        assert_eq!(
+            t.transform_homogeneous(&Vector4::new(1, 2, 3, 0)),
+            Vector4::new(1, -3, 2, 0)
+        );
+    }
+
+    #[test]
+    fn se3_adjoint() {
+        let t = Matrix4::new(1, 0, 0, 0, 0, 0, -1, 0, 0, 1, 0, 3, 0, 0, 0, 1).to_se3();
+        let tar = Matrix6::new(
+            1, 0, 0, 0, 0, 0, 0, 0, -1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 3, 1, 0, 0, 3, 0, 0, 0, 0,
+            -1, 0, 0, 0, 0, 1, 0,
+        );
+        assert_eq!(t.adjoint(), tar);
+        assert_eq!(t.coadjoint(), tar.transpose());
+        assert_eq!(t.adjoint() * t.adjoint_inv(), Matrix6::identity());
+    }
+
+    #[test]
+    fn se3_transform_twist() {
+        let t = [0.3, -0.1, 0.5, 1.0, 2.0, -1.0].to_bracket().exp();
+        let v_b = [0.1, 0.2, -0.3, 0.5, -0.4, 1.0].to_twist();
+        let v_a = t.transform_twist(&v_b);
+        let tar = t.0 * v_b.to_bracket().0 * t.inv().0;
+        assert!(near_zero((v_a.to_bracket().0 - tar).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn se3_transform_wrench() {
+        let t = [0.3, -0.1, 0.5, 1.0, 2.0, -1.0].to_bracket().exp();
+        let v_b = [0.1, 0.2, -0.3, 0.5, -0.4, 1.0].to_twist();
+        let f_b = [1.0, -2.0, 0.5, 3.0, 0.0, -1.0].to_wrench();
+        let v_a = t.transform_twist(&v_b);
+        let f_a = t.transform_wrench(&f_b);
+        let power_diff: f64 = f_a.power(&v_a) - f_b.power(&v_b);
+        assert!(near_zero(power_diff.abs(), Some(1e-9)));
+    }
+
+    #[test]
+    fn se3_log() {
+        let t = Matrix4::new(
+            1.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 1.0, 0.0, 3.0, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3();
+        let tar = [FRAC_PI_2, 0.0, 0.0, 0.0, 3.0 * FRAC_PI_4, 3.0 * FRAC_PI_4].to_bracket();
+        assert!(near_zero((t.log().0 - tar.0).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn se3_log_translation() {
+        let t = Matrix4::new(
+            1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 2.0, 0.0, 0.0, 1.0, 3.0, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3();
+        assert_eq!(t.log().to_vec(), Vector6::new(0.0, 0.0, 0.0, 1.0, 2.0, 3.0));
     }
 
     #[test]
-    fn se3_to_twist() {
-        let se3_mat = Matrix4::new(0, -3, 2, 4, 3, 0, -1, 5, -2, 1, 0, 6, 0, 0, 0, 1).to_se3();
-        let target_twist = Vector6::new(1, 2, 3, 4, 5, 6).to_twist();
-        assert_eq!(se3_mat.to_twist().0, target_twist.0);
+    fn se3_log_exp() {
+        let t = [0.2, -0.4, 0.7, 1.0, -2.0, 0.5].to_bracket().exp();
+        assert!(near_zero((t.log().exp().0 - t.0).norm(), Some(1e-9)));
     }
 }
@@ -1,5 +1,32 @@
+extern crate nalgebra as na;
+use na::{Point3, Scalar, Vector3, Vector4};
+
 pub trait ToVec<T> {
     type VecSize;
 
     fn to_vec(&self) -> Self::VecSize;
 }
+
+pub trait MatrixExp<T> {
+    type Output;
+
+    fn exp(&self) -> Self::Output;
+}
+
+pub trait MatrixLog<T> {
+    type Output;
+
+    fn log(&self) -> Self::Output;
+}
+
+pub trait Inverse<T> {
+    type Output;
+
+    fn inv(&self) -> Self::Output;
+}
+
+pub trait GroupAction<T: Scalar> {
+    fn transform_point(&self, point: &Point3<T>) -> Point3<T>;
+    fn transform_vector(&self, vector: &Vector3<T>) -> Vector3<T>;
+    fn transform_homogeneous(&self, vector: &Vector4<T>) -> Vector4<T>;
+}
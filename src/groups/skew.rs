@@ -0,0 +1,162 @@
+extern crate nalgebra as na;
+use crate::groups::{MatrixExp, So3Matrix, ToVec};
+use crate::interfaces::Numeric;
+use crate::transforms::axis_ang_3;
+use na::{ComplexField, Const, Matrix, Matrix3, Vector3, ViewStorage};
+use num::Zero;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct So3Skew<T: Numeric<T>>(pub Matrix3<T>);
+
+pub trait ToSkew<T: Numeric<T>> {
+    fn to_skew(&self) -> So3Skew<T>;
+}
+
+impl<T: Numeric<T>> ToSkew<T> for Matrix3<T> {
+    fn to_skew(&self) -> So3Skew<T> {
+        So3Skew(*self)
+    }
+}
+
+impl<T: Numeric<T>> ToSkew<T> for Vector3<T> {
+    fn to_skew(&self) -> So3Skew<T> {
+        So3Skew(Matrix3::new(
+            Zero::zero(),
+            -self[2],
+            self[1],
+            self[2],
+            Zero::zero(),
+            -self[0],
+            -self[1],
+            self[0],
+            Zero::zero(),
+        ))
+    }
+}
+
+impl<T: Numeric<T>> ToSkew<T>
+    for Matrix<T, Const<3>, Const<1>, ViewStorage<'_, T, Const<3>, Const<1>, Const<1>, Const<6>>>
+{
+    fn to_skew(&self) -> So3Skew<T> {
+        So3Skew(Matrix3::new(
+            Zero::zero(),
+            -self[(2, 0)],
+            self[(1, 0)],
+            self[(2, 0)],
+            Zero::zero(),
+            -self[(0, 0)],
+            -self[(1, 0)],
+            self[(0, 0)],
+            Zero::zero(),
+        ))
+    }
+}
+
+impl<T: Numeric<T>> ToSkew<T> for [T; 3] {
+    fn to_skew(&self) -> So3Skew<T> {
+        So3Skew(Matrix3::new(
+            Zero::zero(),
+            -self[2],
+            self[1],
+            self[2],
+            Zero::zero(),
+            -self[0],
+            -self[1],
+            self[0],
+            Zero::zero(),
+        ))
+    }
+}
+
+impl<T: Numeric<T>> ToVec<T> for So3Skew<T> {
+    type VecSize = Vector3<T>;
+
+    fn to_vec(&self) -> Vector3<T> {
+        Vector3::new(self.0.m32, self.0.m13, self.0.m21)
+    }
+}
+
+impl<T: Numeric<T>> MatrixExp<T> for So3Skew<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    type Output = So3Matrix<T>;
+
+    fn exp(&self) -> So3Matrix<T> {
+        let theta = match axis_ang_3(self.to_vec()) {
+            Some((_, theta)) => theta,
+            None => return So3Matrix(Matrix3::identity()),
+        };
+        let omgmat = self.0 / theta;
+        So3Matrix(
+            Matrix3::identity() + omgmat * theta.sin() + omgmat * omgmat * (T::one() - theta.cos()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::MatrixLog;
+    use crate::helpers::near_zero;
+
+    #[test]
+    fn array_to_skew() {
+        let test_mat = Matrix3::new(0.0, -3.3, 2.2, 3.3, 0.0, -1.1, -2.2, 1.1, 0.0);
+        let vec: [f64; 3] = [1.1, 2.2, 3.3];
+        let res = vec.to_skew();
+        assert_eq!(res.0, test_mat);
+    }
+
+    #[test]
+    fn vector3_to_skew() {
+        let t_vec = Vector3::new(1, 2, 3);
+        let t_mat = t_vec.to_skew();
+        let target = Matrix3::new(0, -3, 2, 3, 0, -1, -2, 1, 0);
+        assert_eq!(t_mat.0, target);
+    }
+
+    #[test]
+    fn matrix_to_skew() {
+        let target = Matrix3::new(0, -3, 2, 3, 0, -1, -2, 1, 0);
+        let res = target.to_skew();
+        assert_eq!(res.0, target);
+    }
+
+    #[test]
+    fn skew_to_vec() {
+        let skew = Matrix3::new(0, -3, 2, 3, 0, -1, -2, 1, 0).to_skew();
+        let tar = Vector3::new(1, 2, 3);
+        assert_eq!(skew.to_vec(), tar);
+    }
+
+    #[test]
+    fn skew_exp() {
+        let skew = Matrix3::new(0.0, -3.0, 2.0, 3.0, 0.0, -1.0, -2.0, 1.0, 0.0).to_skew();
+        let tar = Matrix3::new(
+            -0.69492056,
+            0.71352099,
+            0.08929286,
+            -0.19200697,
+            -0.30378504,
+            0.93319235,
+            0.69297817,
+            0.6313497,
+            0.34810748,
+        );
+        assert!(near_zero((skew.exp().0 - tar).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn skew_exp_zero() {
+        let skew = [0.0, 0.0, 0.0].to_skew();
+        assert_eq!(skew.exp().0, Matrix3::identity());
+    }
+
+    #[test]
+    fn skew_exp_log() {
+        let skew = [0.3, -0.2, 0.9].to_skew();
+        assert!(near_zero((skew.exp().log().0 - skew.0).norm(), Some(1e-9)));
+    }
+}
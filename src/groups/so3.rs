@@ -1,82 +1,100 @@
 extern crate nalgebra as na;
-use crate::bcat;
 use crate::concat::{allocate_block_output, Block, HCat, VCat};
-use crate::groups::{Se3Matrix, ToVec};
+use crate::errors::GroupError;
+use crate::groups::{GroupAction, Inverse, MatrixLog, Se3Matrix, So3Skew, ToSkew};
+use crate::helpers::near_zero;
 use crate::interfaces::Numeric;
-use na::{Const, Matrix, Matrix3, RowVector4, Vector3, ViewStorage};
+use na::{ClosedAdd, ClosedMul, ComplexField, Matrix3, Point3, RowVector4, Vector3, Vector4};
 use num::{One, Zero};
+use std::ops::Mul;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct So3Matrix<T: Numeric<T>>(pub Matrix3<T>);
 
 pub trait ToSo3<T: Numeric<T>> {
     fn to_so3(&self) -> So3Matrix<T>;
 }
 
+pub trait TryToSo3<T: Numeric<T>> {
+    fn try_to_so3(&self) -> Result<So3Matrix<T>, GroupError>;
+}
+
 pub trait So3ToSe3<T: Numeric<T>> {
     fn to_se3(&self, p_vec: Vector3<T>) -> Se3Matrix<T>;
 }
 
+pub trait So3Distance<T: Numeric<T>> {
+    fn distance_to_so3(&self) -> T;
+    fn is_so3(&self) -> bool;
+    fn project_to_so3(&self) -> So3Matrix<T>;
+}
+
 impl<T: Numeric<T>> ToSo3<T> for Matrix3<T> {
     fn to_so3(&self) -> So3Matrix<T> {
         So3Matrix(*self)
     }
 }
 
-impl<T: Numeric<T>> ToSo3<T> for Vector3<T> {
-    fn to_so3(&self) -> So3Matrix<T> {
-        So3Matrix(Matrix3::new(
-            Zero::zero(),
-            -self[2],
-            self[1],
-            self[2],
-            Zero::zero(),
-            -self[0],
-            -self[1],
-            self[0],
-            Zero::zero(),
-        ))
+impl<T: Numeric<T>> TryToSo3<T> for Matrix3<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    fn try_to_so3(&self) -> Result<So3Matrix<T>, GroupError> {
+        if self.determinant() <= T::zero() {
+            Err(GroupError::NotRightHanded)
+        } else if !self.is_so3() {
+            Err(GroupError::NotOrthonormal)
+        } else {
+            Ok(So3Matrix(*self))
+        }
     }
 }
 
-impl<T: Numeric<T>> ToSo3<T>
-    for Matrix<T, Const<3>, Const<1>, ViewStorage<'_, T, Const<3>, Const<1>, Const<1>, Const<6>>>
+impl<T: Numeric<T>> So3Distance<T> for Matrix3<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
 {
-    fn to_so3(&self) -> So3Matrix<T> {
-        So3Matrix(Matrix3::new(
-            Zero::zero(),
-            -self[(2, 0)],
-            self[(1, 0)],
-            self[(2, 0)],
-            Zero::zero(),
-            -self[(0, 0)],
-            -self[(1, 0)],
-            self[(0, 0)],
-            Zero::zero(),
-        ))
+    fn distance_to_so3(&self) -> T {
+        if self.determinant() > T::zero() {
+            (self.transpose() * self - Matrix3::identity()).norm()
+        } else {
+            na::convert(1e9)
+        }
     }
-}
 
-impl<T: Numeric<T>> ToSo3<T> for [T; 3] {
-    fn to_so3(&self) -> So3Matrix<T> {
-        So3Matrix(Matrix3::new(
-            Zero::zero(),
-            -self[2],
-            self[1],
-            self[2],
-            Zero::zero(),
-            -self[0],
-            -self[1],
-            self[0],
-            Zero::zero(),
-        ))
+    fn is_so3(&self) -> bool {
+        near_zero(self.distance_to_so3(), Some(na::convert(1e-3)))
+    }
+
+    fn project_to_so3(&self) -> So3Matrix<T> {
+        let svd = self.svd(true, true);
+        let mut u = svd.u.unwrap();
+        let v_t = svd.v_t.unwrap();
+        // Flip the direction paired with the smallest singular value to land on det +1
+        if (u * v_t).determinant() < T::zero() {
+            u.column_mut(2).neg_mut();
+        }
+        So3Matrix(u * v_t)
     }
 }
 
-impl<T: Numeric<T>> ToVec<T> for So3Matrix<T> {
-    type VecSize = Vector3<T>;
+impl<T: Numeric<T>> So3Distance<T> for So3Matrix<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    fn distance_to_so3(&self) -> T {
+        self.0.distance_to_so3()
+    }
+
+    fn is_so3(&self) -> bool {
+        self.0.is_so3()
+    }
 
-    fn to_vec(&self) -> Vector3<T> {
-        Vector3::new(self.0.m32, self.0.m13, self.0.m21)
+    fn project_to_so3(&self) -> So3Matrix<T> {
+        self.0.project_to_so3()
     }
 }
 
@@ -87,47 +105,200 @@ impl<T: Numeric<T>> So3ToSe3<T> for So3Matrix<T> {
     }
 }
 
+impl<T: Numeric<T>> Inverse<T> for So3Matrix<T> {
+    type Output = So3Matrix<T>;
+
+    fn inv(&self) -> So3Matrix<T> {
+        So3Matrix(self.0.transpose())
+    }
+}
+
+impl<T: Numeric<T> + ClosedAdd + ClosedMul> Mul for So3Matrix<T> {
+    type Output = So3Matrix<T>;
+
+    fn mul(self, rhs: So3Matrix<T>) -> So3Matrix<T> {
+        So3Matrix(self.0 * rhs.0)
+    }
+}
+
+impl<T: Numeric<T> + ClosedAdd + ClosedMul> GroupAction<T> for So3Matrix<T> {
+    fn transform_point(&self, point: &Point3<T>) -> Point3<T> {
+        Point3::from(self.0 * point.coords)
+    }
+
+    fn transform_vector(&self, vector: &Vector3<T>) -> Vector3<T> {
+        self.0 * vector
+    }
+
+    fn transform_homogeneous(&self, vector: &Vector4<T>) -> Vector4<T> {
+        let rotated = self.0 * vector.xyz();
+        Vector4::new(rotated[0], rotated[1], rotated[2], vector[3])
+    }
+}
+
+impl<T: Numeric<T>> MatrixLog<T> for So3Matrix<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    type Output = So3Skew<T>;
+
+    fn log(&self) -> So3Skew<T> {
+        let r = &self.0;
+        let two = T::one() + T::one();
+        let acosinput = (r.trace() - T::one()) / two;
+        if acosinput >= T::one() {
+            So3Skew(Matrix3::zeros())
+        } else if acosinput <= -T::one() {
+            // theta = pi, the axis is recovered from whichever column of R + I is well conditioned
+            let omg = if !near_zero(T::one() + r.m33, None) {
+                Vector3::new(r.m13, r.m23, T::one() + r.m33) / (two * (T::one() + r.m33)).sqrt()
+            } else if !near_zero(T::one() + r.m22, None) {
+                Vector3::new(r.m12, T::one() + r.m22, r.m32) / (two * (T::one() + r.m22)).sqrt()
+            } else {
+                Vector3::new(T::one() + r.m11, r.m21, r.m31) / (two * (T::one() + r.m11)).sqrt()
+            };
+            let pi: T = na::convert(std::f64::consts::PI);
+            (omg * pi).to_skew()
+        } else {
+            let theta = acosinput.acos();
+            So3Skew((r - r.transpose()) * (theta / (two * theta.sin())))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::groups::ToSe3;
-    use na::Vector6;
+    use na::Matrix4;
 
     #[test]
-    fn array_to_so3() {
-        let test_mat = Matrix3::new(0.0, -3.3, 2.2, 3.3, 0.0, -1.1, -2.2, 1.1, 0.0);
-        let vec: [f64; 3] = [1.1, 2.2, 3.3];
-        let res = vec.to_so3();
-        assert_eq!(res.0, test_mat);
+    fn matrix_to_so3() {
+        let target = Matrix3::new(1, 0, 0, 0, 0, -1, 0, 1, 0);
+        let res = target.to_so3();
+        assert_eq!(res.0, target);
     }
 
     #[test]
-    fn vector3_to_so3() {
-        let t_vec = Vector3::new(1, 2, 3);
-        let t_mat = t_vec.to_so3();
-        let target = Matrix3::new(0, -3, 2, 3, 0, -1, -2, 1, 0);
-        assert_eq!(t_mat.0, target);
+    fn matrix_try_to_so3() {
+        let r = Matrix3::new(0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0);
+        assert_eq!(r.try_to_so3().unwrap().0, r);
+        let skewed = Matrix3::new(1.0, 0.0, 0.0, 0.0, 0.1, -0.95, 0.0, 1.0, 0.1);
+        assert_eq!(skewed.try_to_so3().err(), Some(GroupError::NotOrthonormal));
+        let reflection = Matrix3::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, -1.0);
+        assert_eq!(
+            reflection.try_to_so3().err(),
+            Some(GroupError::NotRightHanded)
+        );
     }
 
     #[test]
-    fn matrix_to_so3() {
-        let target = Matrix3::new(0, -3, 2, 3, 0, -1, -2, 1, 0);
-        let res = target.to_so3();
-        assert_eq!(res.0, target);
+    fn distance_to_so3() {
+        let mat = Matrix3::new(1.0, 0.0, 0.0, 0.0, 0.1, -0.95, 0.0, 1.0, 0.1);
+        assert!(near_zero(mat.distance_to_so3() - 0.08835, Some(1e-5)));
+        assert!(!mat.is_so3());
+        assert!(Matrix3::<f64>::identity().to_so3().is_so3());
+    }
+
+    #[test]
+    fn project_to_so3() {
+        let mat = Matrix3::new(
+            0.675, 0.150, 0.720, 0.370, 0.771, -0.511, -0.630, 0.619, 0.472,
+        );
+        let tar = Matrix3::new(
+            0.67901136,
+            0.14894516,
+            0.71885945,
+            0.37320708,
+            0.77319584,
+            -0.51272279,
+            -0.63218672,
+            0.61642804,
+            0.46942137,
+        );
+        let res = mat.to_so3().project_to_so3();
+        assert!(near_zero((res.0 - tar).norm(), Some(1e-6)));
+        assert!(res.is_so3());
     }
 
     #[test]
-    fn so3_to_vec() {
-        let so3_mat = Matrix3::new(0, -3, 2, 3, 0, -1, -2, 1, 0).to_so3();
-        let tar = Vector3::new(1, 2, 3);
-        assert_eq!(so3_mat.to_vec(), tar);
+    fn project_to_so3_reflection() {
+        let mat = Matrix3::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, -1.0);
+        let res = mat.project_to_so3();
+        assert!(near_zero(res.0.determinant() - 1.0, Some(1e-9)));
     }
 
     #[test]
     fn so3_to_se3() {
-        let so3_mat = [1, 2, 3].to_so3();
+        let so3_mat = Matrix3::new(1, 0, 0, 0, 0, -1, 0, 1, 0).to_so3();
         let p_vec = Vector3::new(4, 5, 6);
-        let tar = Vector6::new(1, 2, 3, 4, 5, 6).to_se3();
+        let tar = Matrix4::new(1, 0, 0, 4, 0, 0, -1, 5, 0, 1, 0, 6, 0, 0, 0, 1).to_se3();
         assert_eq!(tar.0, so3_mat.to_se3(p_vec).0);
     }
+
+    #[test]
+    fn so3_inv() {
+        let r = Matrix3::new(0, 0, 1, 1, 0, 0, 0, 1, 0).to_so3();
+        let tar = Matrix3::new(0, 1, 0, 0, 0, 1, 1, 0, 0);
+        assert_eq!(r.inv().0, tar);
+        assert_eq!((r * r.inv()).0, Matrix3::identity());
+    }
+
+    #[test]
+    fn so3_mul() {
+        let r = Matrix3::new(0, 0, 1, 1, 0, 0, 0, 1, 0).to_so3();
+        let tar = Matrix3::new(0, 1, 0, 0, 0, 1, 1, 0, 0);
+        assert_eq!((r * r).0, tar);
+    }
+
+    #[test]
+    fn so3_action() {
+        let r = Matrix3::new(0, -1, 0, 1, 0, 0, 0, 0, 1).to_so3();
+        assert_eq!(
+            r.transform_point(&Point3::new(1, 2, 3)),
+            Point3::new(-2, 1, 3)
+        );
+        assert_eq!(
+            r.transform_vector(&Vector3::new(1, 0, 0)),
+            Vector3::new(0, 1, 0)
+        );
+        assert_eq!(
+            r.transform_homogeneous(&Vector4::new(1, 2, 3, 0)),
+            Vector4::new(-2, 1, 3, 0)
+        );
+    }
+
+    #[test]
+    fn so3_log() {
+        let r = Matrix3::new(0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0).to_so3();
+        let tar = Matrix3::new(
+            0.0,
+            -1.20919958,
+            1.20919958,
+            1.20919958,
+            0.0,
+            -1.20919958,
+            -1.20919958,
+            1.20919958,
+            0.0,
+        );
+        assert!(near_zero((r.log().0 - tar).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn so3_log_identity() {
+        let r = Matrix3::<f64>::identity().to_so3();
+        assert_eq!(r.log().0, Matrix3::zeros());
+    }
+
+    #[test]
+    fn so3_log_pi() {
+        let r = Matrix3::new(1.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, -1.0).to_so3();
+        let tar = [std::f64::consts::PI, 0.0, 0.0].to_skew();
+        assert!(near_zero((r.log().0 - tar.0).norm(), Some(1e-6)));
+        let r = Matrix3::new(-1.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 1.0).to_so3();
+        let tar = [0.0, 0.0, std::f64::consts::PI].to_skew();
+        assert!(near_zero((r.log().0 - tar.0).norm(), Some(1e-6)));
+    }
 }
@@ -1,10 +1,10 @@
 extern crate nalgebra as na;
-use crate::bcat;
 use crate::concat::{allocate_block_output, Block, HCat, VCat};
-use crate::groups::{Se3Matrix, ToSe3, ToSo3};
+use crate::groups::{MatrixExp, Se3Bracket, Se3Matrix, ToBracket, ToSkew};
 use crate::interfaces::Numeric;
-use na::{Matrix3, Matrix6, Vector6};
+use na::{ComplexField, Matrix3, Matrix6, Vector6};
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Twist<T: Numeric<T>>(pub Vector6<T>);
 
 pub trait ToTwist<T: Numeric<T>> {
@@ -29,23 +29,36 @@ impl<T: Numeric<T>> ToTwist<T> for [T; 6] {
 
 impl<T: Numeric<T>> Ad<T> for Twist<T> {
     fn ad(&self) -> Matrix6<T> {
-        let omega = self.0.fixed_view::<3, 1>(0, 0).to_so3();
-        let vmat = self.0.fixed_view::<3, 1>(3, 0).to_so3();
+        let omega = self.0.fixed_view::<3, 1>(0, 0).to_skew();
+        let vmat = self.0.fixed_view::<3, 1>(3, 0).to_skew();
         let zeros = Matrix3::zeros();
-        return bcat![omega.0, zeros;
-                    vmat.0, omega.0];
+        bcat![omega.0, zeros;
+              vmat.0, omega.0]
     }
 }
 
-impl<T: Numeric<T>> ToSe3<T> for Twist<T> {
-    fn to_se3(&self) -> Se3Matrix<T> {
-        self.0.to_se3()
+impl<T: Numeric<T>> ToBracket<T> for Twist<T> {
+    fn to_bracket(&self) -> Se3Bracket<T> {
+        self.0.to_bracket()
+    }
+}
+
+impl<T: Numeric<T>> MatrixExp<T> for Twist<T>
+where
+    T: ComplexField<RealField = T>,
+    f64: From<T>,
+{
+    type Output = Se3Matrix<T>;
+
+    fn exp(&self) -> Se3Matrix<T> {
+        self.to_bracket().exp()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::helpers::near_zero;
     use na::Matrix4;
 
     #[test]
@@ -62,10 +75,10 @@ mod tests {
     }
 
     #[test]
-    fn se3_from_twist() {
-        let test_mat = Matrix4::new(0, -3, 2, 4, 3, 0, -1, 5, -2, 1, 0, 6, 0, 0, 0, 1);
+    fn bracket_from_twist() {
+        let test_mat = Matrix4::new(0, -3, 2, 4, 3, 0, -1, 5, -2, 1, 0, 6, 0, 0, 0, 0);
         let vec = Vector6::new(1, 2, 3, 4, 5, 6).to_twist();
-        let res = vec.to_se3();
+        let res = vec.to_bracket();
         assert_eq!(res.0, test_mat);
     }
     #[test]
@@ -79,4 +92,15 @@ mod tests {
         );
         assert_eq!(res, test_mat);
     }
+
+    #[test]
+    fn twist_exp() {
+        let theta = std::f64::consts::PI;
+        let screw = Vector6::new(0.0, 0.0, 1.0, 0.0, -1.0, 0.0);
+        let res = (screw * theta).to_twist().exp();
+        let tar = Matrix4::new(
+            -1.0, 0.0, 0.0, 2.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        );
+        assert!(near_zero((res.0 - tar).norm(), Some(1e-9)));
+    }
 }
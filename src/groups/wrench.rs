@@ -0,0 +1,97 @@
+extern crate nalgebra as na;
+use crate::groups::{Ad, Twist};
+use crate::interfaces::Numeric;
+use na::{ClosedAdd, ClosedMul, Vector6};
+use std::ops::Add;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Wrench<T: Numeric<T>>(pub Vector6<T>);
+
+pub trait ToWrench<T: Numeric<T>> {
+    fn to_wrench(&self) -> Wrench<T>;
+}
+
+pub trait Power<T: Numeric<T>> {
+    fn power(&self, twist: &Twist<T>) -> T;
+}
+
+pub trait CoAd<T: Numeric<T>> {
+    fn coad(&self, twist: &Twist<T>) -> Wrench<T>;
+}
+
+impl<T: Numeric<T>> ToWrench<T> for Vector6<T> {
+    fn to_wrench(&self) -> Wrench<T> {
+        Wrench(*self)
+    }
+}
+
+impl<T: Numeric<T>> ToWrench<T> for [T; 6] {
+    fn to_wrench(&self) -> Wrench<T> {
+        Wrench(Vector6::from_vec(self.to_vec()))
+    }
+}
+
+impl<T: Numeric<T> + ClosedAdd> Add for Wrench<T> {
+    type Output = Wrench<T>;
+
+    fn add(self, rhs: Wrench<T>) -> Wrench<T> {
+        Wrench(self.0 + rhs.0)
+    }
+}
+
+impl<T: Numeric<T> + ClosedAdd + ClosedMul> Power<T> for Wrench<T> {
+    fn power(&self, twist: &Twist<T>) -> T {
+        self.0.dot(&twist.0)
+    }
+}
+
+impl<T: Numeric<T> + ClosedAdd + ClosedMul> CoAd<T> for Wrench<T> {
+    fn coad(&self, twist: &Twist<T>) -> Wrench<T> {
+        Wrench(twist.ad().transpose() * self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::ToTwist;
+    use crate::helpers::near_zero;
+
+    #[test]
+    fn array_to_wrench() {
+        let arr: [f64; 6] = [1.1, 2.2, 3.3, 4.4, 5.5, 6.6];
+        let res = arr.to_wrench();
+        assert_eq!(res.0, Vector6::new(1.1, 2.2, 3.3, 4.4, 5.5, 6.6));
+    }
+
+    #[test]
+    fn vector_to_wrench() {
+        let vec = Vector6::new(1, 2, 3, 4, 5, 6);
+        let res = vec.to_wrench();
+        assert_eq!(res.0, Vector6::new(1, 2, 3, 4, 5, 6));
+    }
+
+    #[test]
+    fn wrench_add() {
+        let res = [1, 2, 3, 4, 5, 6].to_wrench() + [6, 5, 4, 3, 2, 1].to_wrench();
+        assert_eq!(res.0, Vector6::new(7, 7, 7, 7, 7, 7));
+    }
+
+    #[test]
+    fn wrench_power() {
+        let wrench = [1, 0, 2, 0, 3, 1].to_wrench();
+        let twist = [1, 2, 3, 4, 5, 6].to_twist();
+        assert_eq!(wrench.power(&twist), 28);
+    }
+
+    #[test]
+    fn wrench_coad() {
+        let wrench = [1.0, 0.0, 2.0, 0.0, 3.0, 1.0].to_wrench();
+        let v1 = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0].to_twist();
+        let v2 = [0.5, -1.0, 0.0, 2.0, 1.0, -3.0].to_twist();
+        // <ad_V1^T F, V2> = <F, ad_V1 V2>
+        let lhs: f64 = wrench.coad(&v1).power(&v2);
+        let rhs = wrench.power(&(v1.ad() * v2.0).to_twist());
+        assert!(near_zero((lhs - rhs).abs(), Some(1e-9)));
+    }
+}
@@ -3,15 +3,15 @@ where
     f64: std::convert::From<T>,
 {
     match eps {
-        Some(ep) => f64::from(x) < f64::from(ep),
-        None => f64::from(x) < 1e-6,
+        Some(ep) => f64::from(x).abs() < f64::from(ep),
+        None => f64::from(x).abs() < 1e-6,
     }
 }
 
 pub fn near_zero_float(x: f64, eps: Option<f64>) -> bool {
     match eps {
-        Some(ep) => x < ep,
-        None => x < 1e-6,
+        Some(ep) => x.abs() < ep,
+        None => x.abs() < 1e-6,
     }
 }
 
@@ -38,4 +38,17 @@ mod tests {
     fn near_zero_fail() {
         assert!(!near_zero(3, None))
     }
+
+    #[test]
+    fn near_zero_negative() {
+        assert!(near_zero(-1e-7, None));
+        assert!(!near_zero(-3, None));
+        assert!(!near_zero(-1e-2, Some(1e-3)))
+    }
+
+    #[test]
+    fn near_zero_float_negative() {
+        assert!(near_zero_float(-1e-7, None));
+        assert!(!near_zero_float(-3.0, None))
+    }
 }
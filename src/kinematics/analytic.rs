@@ -0,0 +1,482 @@
+extern crate nalgebra as na;
+use crate::groups::{
+    GroupAction, Inverse, MatrixExp, ScrewAxis, Se3Matrix, So3Matrix, ToSo3, ToTwist,
+};
+use crate::interfaces::Numeric;
+use crate::kinematics::SerialChain;
+use na::{Point3, RealField, Vector3, Vector6, U6};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArmGeometry {
+    // Joints 1 and 2 intersect, joints 4, 5 and 6 meet at the wrist centre (PUMA style)
+    SphericalWrist,
+    // Joints 2, 3, 4 and 6 are parallel and joints 5 and 6 intersect (UR style)
+    UrLike,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnalyticArm<T: Numeric<T>> {
+    chain: SerialChain<T, U6>,
+    geometry: ArmGeometry,
+    // A point on each joint axis and its unit direction, in the space frame at home
+    axes: [(Vector3<T>, Vector3<T>); 6],
+    // Wrist centre for SphericalWrist, intersection of joints 5 and 6 for UrLike
+    wrist: Vector3<T>,
+    // Intersection of joints 1 and 2, only used for SphericalWrist
+    shoulder: Vector3<T>,
+}
+
+fn tolerance<T: RealField>() -> T {
+    na::convert(1e-6)
+}
+
+fn wrap<T: RealField>(theta: T) -> T {
+    theta.clone().sin().atan2(theta.cos())
+}
+
+fn perpendicular<T: Numeric<T> + RealField>(omega: &Vector3<T>) -> Vector3<T> {
+    let n = omega.cross(&Vector3::x());
+    if n.norm() > na::convert(0.5) {
+        n.normalize()
+    } else {
+        omega.cross(&Vector3::y()).normalize()
+    }
+}
+
+fn is_parallel<T: Numeric<T> + RealField>(a: &Vector3<T>, b: &Vector3<T>) -> bool {
+    a.cross(b).norm() < tolerance()
+}
+
+fn distance_to_line<T: Numeric<T> + RealField>(
+    (q, omega): &(Vector3<T>, Vector3<T>),
+    p: &Vector3<T>,
+) -> T {
+    (p - q).cross(omega).norm()
+}
+
+// Common point of two non-parallel axes, if they intersect
+fn intersection<T: Numeric<T> + RealField>(
+    (qa, wa): &(Vector3<T>, Vector3<T>),
+    (qb, wb): &(Vector3<T>, Vector3<T>),
+) -> Option<Vector3<T>> {
+    let n = wa.cross(wb);
+    let n_sq = n.norm_squared();
+    if n_sq < tolerance() {
+        return None;
+    }
+    let d = qb - qa;
+    if d.dot(&n).abs() / n_sq.sqrt() > tolerance() {
+        return None;
+    }
+    Some(qa + wa * (d.cross(wb).dot(&n) / n_sq))
+}
+
+// Paden-Kahan subproblem 1: rotate p onto q about the axis (r, omega)
+fn subproblem1<T: Numeric<T> + RealField>(
+    r: &Vector3<T>,
+    omega: &Vector3<T>,
+    p: &Vector3<T>,
+    q: &Vector3<T>,
+) -> T {
+    let u = p - r;
+    let v = q - r;
+    let up = u - omega * omega.dot(&u);
+    let vp = v - omega * omega.dot(&v);
+    omega.dot(&up.cross(&vp)).atan2(up.dot(&vp))
+}
+
+// Paden-Kahan subproblem 2: e^[w1]t1 e^[w2]t2 p = q for two axes meeting at r
+fn subproblem2<T: Numeric<T> + RealField>(
+    r: &Vector3<T>,
+    w1: &Vector3<T>,
+    w2: &Vector3<T>,
+    p: &Vector3<T>,
+    q: &Vector3<T>,
+) -> Vec<(T, T)> {
+    let u = p - r;
+    let v = q - r;
+    let c = w1.dot(w2);
+    let a = w1.dot(&v);
+    let b = w2.dot(&u);
+    let den = T::one() - c * c;
+    let alpha = (a - c * b) / den;
+    let beta = (b - c * a) / den;
+    let n = w1.cross(w2);
+    let two: T = na::convert(2.0);
+    let gamma_sq = (u.norm_squared() - alpha * alpha - beta * beta - two * alpha * beta * c)
+        / n.norm_squared();
+    if gamma_sq < -tolerance::<T>() {
+        return Vec::new();
+    }
+    let gamma = gamma_sq.max(T::zero()).sqrt();
+    let gammas = if gamma < tolerance() {
+        vec![T::zero()]
+    } else {
+        vec![gamma, -gamma]
+    };
+    gammas
+        .into_iter()
+        .map(|g| {
+            let mid = r + w1 * alpha + w2 * beta + n * g;
+            (subproblem1(r, w1, &mid, q), subproblem1(r, w2, p, &mid))
+        })
+        .collect()
+}
+
+// Paden-Kahan subproblem 3: |e^[w]t p - q| = delta about the axis (r, omega)
+fn subproblem3<T: Numeric<T> + RealField>(
+    r: &Vector3<T>,
+    omega: &Vector3<T>,
+    p: &Vector3<T>,
+    q: &Vector3<T>,
+    delta: T,
+) -> Vec<T> {
+    let u = p - r;
+    let v = q - r;
+    let up = u - omega * omega.dot(&u);
+    let vp = v - omega * omega.dot(&v);
+    let (nu, nv) = (up.norm(), vp.norm());
+    if nu < tolerance() || nv < tolerance() {
+        return vec![T::zero()];
+    }
+    let along = omega.dot(&(p - q));
+    let delta_sq = delta * delta - along * along;
+    let theta0 = omega.dot(&up.cross(&vp)).atan2(up.dot(&vp));
+    let two: T = na::convert(2.0);
+    angles_about(theta0, (nu * nu + nv * nv - delta_sq) / (two * nu * nv))
+}
+
+// Paden-Kahan subproblem 4: d . (e^[w]t p) = delta about the axis (r, omega)
+fn subproblem4<T: Numeric<T> + RealField>(
+    r: &Vector3<T>,
+    omega: &Vector3<T>,
+    d: &Vector3<T>,
+    p: &Vector3<T>,
+    delta: T,
+) -> Vec<T> {
+    let u = p - r;
+    let along = omega * omega.dot(&u);
+    let up = u - along;
+    let a = d.dot(&up);
+    let b = d.dot(&omega.cross(&up));
+    let norm = (a * a + b * b).sqrt();
+    if norm < tolerance() {
+        return vec![T::zero()];
+    }
+    angles_about(b.atan2(a), (delta - d.dot(r) - d.dot(&along)) / norm)
+}
+
+// Solutions of cos(theta0 - theta) = cos
+fn angles_about<T: Numeric<T> + RealField>(theta0: T, cos: T) -> Vec<T> {
+    if cos.abs() > T::one() + tolerance() {
+        return Vec::new();
+    }
+    let offset = cos.clamp(-T::one(), T::one()).acos();
+    if offset < tolerance() {
+        vec![theta0]
+    } else {
+        vec![theta0 - offset, theta0 + offset]
+    }
+}
+
+impl<T: Numeric<T> + RealField> AnalyticArm<T>
+where
+    f64: From<T>,
+{
+    // Returns None unless every joint is revolute and the axes match one of the supported geometries
+    pub fn new(chain: &SerialChain<T, U6>) -> Option<Self> {
+        let chain = chain.to_space();
+        let mut axes = [(Vector3::zeros(), Vector3::zeros()); 6];
+        for (i, axis) in axes.iter_mut().enumerate() {
+            let screw = ScrewAxis(chain.screw(i).0);
+            let pitch = screw.pitch()?;
+            let (q, omega): (Point3<T>, Vector3<T>) = screw.axis()?;
+            if pitch.abs() > tolerance() {
+                return None;
+            }
+            *axis = (q.coords, omega);
+        }
+
+        let spherical = intersection(&axes[0], &axes[1]).zip(intersection(&axes[3], &axes[4]));
+        if let Some((shoulder, wrist)) = spherical {
+            if distance_to_line(&axes[5], &wrist) < tolerance()
+                && !is_parallel(&axes[4].1, &axes[5].1)
+            {
+                return Some(AnalyticArm {
+                    chain,
+                    geometry: ArmGeometry::SphericalWrist,
+                    axes,
+                    wrist,
+                    shoulder,
+                });
+            }
+        }
+
+        let w = &axes[1].1;
+        if [2, 3, 5].iter().all(|&i| is_parallel(w, &axes[i].1))
+            && !is_parallel(w, &axes[0].1)
+            && !is_parallel(w, &axes[4].1)
+        {
+            let wrist = intersection(&axes[4], &axes[5])?;
+            return Some(AnalyticArm {
+                chain,
+                geometry: ArmGeometry::UrLike,
+                axes,
+                wrist,
+                shoulder: Vector3::zeros(),
+            });
+        }
+        None
+    }
+
+    pub fn geometry(&self) -> ArmGeometry {
+        self.geometry
+    }
+
+    // All distinct solutions in (-pi, pi], nearest to the seed first
+    pub fn solve(&self, t_sd: &Se3Matrix<T>, seed: &Vector6<T>) -> Vec<Vector6<T>> {
+        let g = *t_sd * self.chain.home.inv();
+        let candidates = match self.geometry {
+            ArmGeometry::SphericalWrist => self.solve_spherical_wrist(&g),
+            ArmGeometry::UrLike => self.solve_ur_like(&g, seed),
+        };
+
+        let check: T = na::convert(1e-5);
+        let distance = |theta: &Vector6<T>| theta.zip_map(seed, |a, b| wrap(a - b)).norm();
+        let mut solutions: Vec<Vector6<T>> = Vec::new();
+        for theta in candidates {
+            if theta.iter().any(|t| !t.is_finite()) {
+                continue;
+            }
+            let theta = theta.map(wrap);
+            let error = (self.chain.fkin(&theta).0 - t_sd.0).norm();
+            let duplicate = solutions
+                .iter()
+                .any(|s| s.zip_map(&theta, |a, b| wrap(a - b)).norm() < check);
+            if error < check && !duplicate {
+                solutions.push(theta);
+            }
+        }
+        // Total ordering so that a non-finite seed cannot make the sort panic
+        solutions.sort_by(|a, b| f64::from(distance(a)).total_cmp(&f64::from(distance(b))));
+        solutions
+    }
+
+    fn exp(&self, i: usize, theta: T) -> Se3Matrix<T> {
+        (self.chain.screw(i).0 * theta).to_twist().exp()
+    }
+
+    fn rotation(&self, i: usize, theta: T) -> So3Matrix<T> {
+        self.exp(i, theta).to_so3()
+    }
+
+    // Angle of the joint about axis i that rotates a perpendicular of that axis as r does
+    fn angle_from_rotation(&self, i: usize, r: &So3Matrix<T>) -> T {
+        let omega = &self.axes[i].1;
+        let n = perpendicular(omega);
+        subproblem1(&Vector3::zeros(), omega, &n, &r.transform_vector(&n))
+    }
+
+    fn solve_spherical_wrist(&self, g: &Se3Matrix<T>) -> Vec<Vector6<T>> {
+        let a = &self.axes;
+        let wrist_target = g.transform_point(&Point3::from(self.wrist)).coords;
+        let reach = (wrist_target - self.shoulder).norm();
+        let mut solutions = Vec::new();
+        for t3 in subproblem3(&a[2].0, &a[2].1, &self.wrist, &self.shoulder, reach) {
+            let p = self
+                .exp(2, t3)
+                .transform_point(&Point3::from(self.wrist))
+                .coords;
+            for (t1, t2) in subproblem2(&self.shoulder, &a[0].1, &a[1].1, &p, &wrist_target) {
+                let g2 = (self.exp(0, t1) * self.exp(1, t2) * self.exp(2, t3)).inv() * *g;
+                let p6 = self.wrist + a[5].1;
+                let q6 = g2.transform_point(&Point3::from(p6)).coords;
+                for (t4, t5) in subproblem2(&self.wrist, &a[3].1, &a[4].1, &p6, &q6) {
+                    let g3 = (self.exp(3, t4) * self.exp(4, t5)).inv() * g2;
+                    let t6 = self.angle_from_rotation(5, &g3.to_so3());
+                    solutions.push(Vector6::new(t1, t2, t3, t4, t5, t6));
+                }
+            }
+        }
+        solutions
+    }
+
+    fn solve_ur_like(&self, g: &Se3Matrix<T>, seed: &Vector6<T>) -> Vec<Vector6<T>> {
+        let a = &self.axes;
+        let w = a[1].1;
+        let origin = Vector3::zeros();
+        let wrist_target = g.transform_point(&Point3::from(self.wrist)).coords;
+        let mut solutions = Vec::new();
+        // Joints 2, 3 and 4 preserve the coordinate along their common axis
+        for phi in subproblem4(&a[0].0, &a[0].1, &w, &wrist_target, w.dot(&self.wrist)) {
+            let t1 = -phi;
+            let g2 = self.exp(0, t1).inv() * *g;
+            let r2 = g2.to_so3();
+            let tilt = w.dot(&r2.transform_vector(&w));
+            for t5 in subproblem4(&origin, &a[4].1, &w, &w, tilt) {
+                let u = r2.inv().transform_vector(&w);
+                let v = self.rotation(4, t5).inv().transform_vector(&w);
+                // At a wrist singularity joints 4 and 6 align and the seed picks joint 6
+                let t6 = if (u - a[5].1 * a[5].1.dot(&u)).norm() < tolerance() {
+                    seed[5]
+                } else {
+                    subproblem1(&origin, &a[5].1, &u, &v)
+                };
+                let g3 = g2 * (self.exp(4, t5) * self.exp(5, t6)).inv();
+                let q4 = a[3].0;
+                let q4_target = g3.transform_point(&Point3::from(q4)).coords;
+                let reach = (q4_target - a[1].0).norm();
+                for t3 in subproblem3(&a[2].0, &a[2].1, &q4, &a[1].0, reach) {
+                    let p = self.exp(2, t3).transform_point(&Point3::from(q4)).coords;
+                    let t2 = subproblem1(&a[1].0, &a[1].1, &p, &q4_target);
+                    let g4 = (self.exp(1, t2) * self.exp(2, t3)).inv() * g3;
+                    let t4 = self.angle_from_rotation(3, &g4.to_so3());
+                    solutions.push(Vector6::new(t1, t2, t3, t4, t5, t6));
+                }
+            }
+        }
+        solutions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::ToSe3;
+    use crate::helpers::near_zero;
+    use crate::kinematics::ScrewFrame;
+    use na::{Matrix4, Matrix6};
+
+    fn ur5() -> SerialChain<f64, U6> {
+        let (w1, w2, l1, l2, h1, h2) = (0.109, 0.082, 0.425, 0.392, 0.089, 0.095);
+        let home = Matrix4::new(
+            -1.0,
+            0.0,
+            0.0,
+            l1 + l2,
+            0.0,
+            0.0,
+            1.0,
+            w1 + w2,
+            0.0,
+            1.0,
+            0.0,
+            h1 - h2,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        )
+        .to_se3();
+        let slist = Matrix6::from_columns(&[
+            Vector6::new(0.0, 0.0, 1.0, 0.0, 0.0, 0.0),
+            Vector6::new(0.0, 1.0, 0.0, -h1, 0.0, 0.0),
+            Vector6::new(0.0, 1.0, 0.0, -h1, 0.0, l1),
+            Vector6::new(0.0, 1.0, 0.0, -h1, 0.0, l1 + l2),
+            Vector6::new(0.0, 0.0, -1.0, -w1, l1 + l2, 0.0),
+            Vector6::new(0.0, 1.0, 0.0, h2 - h1, 0.0, l1 + l2),
+        ]);
+        SerialChain::new(home, slist, ScrewFrame::Space)
+    }
+
+    // PUMA style arm with a shoulder offset along the second joint axis
+    fn puma() -> SerialChain<f64, U6> {
+        let home = Matrix4::new(
+            1.0, 0.0, 0.0, 0.42, 0.0, 1.0, 0.0, 0.15, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3();
+        let revolute = |q: Vector3<f64>, w: Vector3<f64>| {
+            let v = -w.cross(&q);
+            Vector6::new(w[0], w[1], w[2], v[0], v[1], v[2])
+        };
+        let wrist = Vector3::new(0.42, 0.15, 0.93);
+        let slist = Matrix6::from_columns(&[
+            revolute(Vector3::zeros(), Vector3::z()),
+            revolute(Vector3::new(0.0, 0.0, 0.5), Vector3::y()),
+            revolute(Vector3::new(0.4, 0.15, 0.5), Vector3::y()),
+            revolute(wrist, Vector3::z()),
+            revolute(wrist, Vector3::y()),
+            revolute(wrist, Vector3::z()),
+        ]);
+        SerialChain::new(home, slist, ScrewFrame::Space)
+    }
+
+    fn check_solutions(chain: &SerialChain<f64, U6>, theta: Vector6<f64>) {
+        let arm = AnalyticArm::new(chain).unwrap();
+        let t_sd = chain.fkin(&theta);
+        let solutions = arm.solve(&t_sd, &theta);
+        assert_eq!(solutions.len(), 8);
+        assert!(near_zero((solutions[0] - theta).norm(), Some(1e-6)));
+        for s in &solutions {
+            assert!(near_zero((chain.fkin(s).0 - t_sd.0).norm(), Some(1e-5)));
+        }
+    }
+
+    #[test]
+    fn analytic_ur5_eight_solutions() {
+        assert_eq!(
+            AnalyticArm::new(&ur5()).unwrap().geometry(),
+            ArmGeometry::UrLike
+        );
+        check_solutions(&ur5(), Vector6::new(0.3, -0.8, 1.2, -0.5, 0.7, 0.4));
+    }
+
+    #[test]
+    fn analytic_spherical_wrist_eight_solutions() {
+        assert_eq!(
+            AnalyticArm::new(&puma()).unwrap().geometry(),
+            ArmGeometry::SphericalWrist
+        );
+        check_solutions(&puma(), Vector6::new(0.4, -0.3, 0.6, 0.5, 0.8, -1.1));
+    }
+
+    #[test]
+    fn analytic_sorted_by_seed() {
+        let chain = ur5();
+        let arm = AnalyticArm::new(&chain).unwrap();
+        let t_sd = chain.fkin(&Vector6::new(0.3, -0.8, 1.2, -0.5, 0.7, 0.4));
+        let seed = Vector6::new(-1.0, 0.5, -0.5, 1.0, -0.5, 2.0);
+        let solutions = arm.solve(&t_sd, &seed);
+        let distance = |s: &Vector6<f64>| s.zip_map(&seed, |a, b| wrap(a - b)).norm();
+        assert!(solutions
+            .windows(2)
+            .all(|pair| distance(&pair[0]) <= distance(&pair[1])));
+    }
+
+    #[test]
+    fn analytic_nan_seed() {
+        let chain = puma();
+        let theta = Vector6::new(0.4, -0.3, 0.6, 0.5, 0.8, -1.1);
+        let arm = AnalyticArm::new(&chain).unwrap();
+        let solutions = arm.solve(&chain.fkin(&theta), &Vector6::repeat(f64::NAN));
+        assert_eq!(solutions.len(), 8);
+        assert!(solutions.iter().all(|s| s.iter().all(|t| t.is_finite())));
+    }
+
+    #[test]
+    fn analytic_body_frame_chain() {
+        let chain = puma().to_body();
+        let theta = Vector6::new(-0.7, 0.2, -0.4, 1.0, -0.6, 0.3);
+        let arm = AnalyticArm::new(&chain).unwrap();
+        let solutions = arm.solve(&chain.fkin(&theta), &theta);
+        assert!(near_zero((solutions[0] - theta).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn analytic_unreachable() {
+        let chain = ur5();
+        let arm = AnalyticArm::new(&chain).unwrap();
+        let mut t_sd = chain.home;
+        t_sd.0[(0, 3)] = 5.0;
+        assert!(arm.solve(&t_sd, &Vector6::zeros()).is_empty());
+    }
+
+    #[test]
+    fn analytic_rejects_general_geometry() {
+        let mut chain = ur5();
+        chain
+            .screws
+            .set_column(2, &Vector6::new(1.0, 0.0, 0.0, 0.0, 0.089, 0.0));
+        assert!(AnalyticArm::new(&chain).is_none());
+    }
+}
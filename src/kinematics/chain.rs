@@ -0,0 +1,211 @@
+extern crate nalgebra as na;
+use crate::groups::{Adjoint, Inverse, MatrixExp, Se3Matrix, ToTwist, Twist};
+use crate::interfaces::Numeric;
+use na::allocator::Allocator;
+use na::{DefaultAllocator, Dim, Matrix6, OMatrix, OVector, RealField, Vector6, U6};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrewFrame {
+    Space,
+    Body,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SerialChain<T: Numeric<T>, N: Dim>
+where
+    DefaultAllocator: Allocator<T, U6, N>,
+{
+    pub home: Se3Matrix<T>,
+    pub screws: OMatrix<T, U6, N>,
+    pub frame: ScrewFrame,
+}
+
+pub fn fkin_space<T, N>(
+    home: &Se3Matrix<T>,
+    slist: &OMatrix<T, U6, N>,
+    theta: &OVector<T, N>,
+) -> Se3Matrix<T>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    let mut t = *home;
+    for i in (0..theta.len()).rev() {
+        let s: Vector6<T> = slist.column(i).into_owned();
+        t = (s * theta[i]).to_twist().exp() * t;
+    }
+    t
+}
+
+pub fn fkin_body<T, N>(
+    home: &Se3Matrix<T>,
+    blist: &OMatrix<T, U6, N>,
+    theta: &OVector<T, N>,
+) -> Se3Matrix<T>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    let mut t = *home;
+    for i in 0..theta.len() {
+        let b: Vector6<T> = blist.column(i).into_owned();
+        t = t * (b * theta[i]).to_twist().exp();
+    }
+    t
+}
+
+impl<T: Numeric<T> + RealField, N: Dim> SerialChain<T, N>
+where
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    pub fn new(home: Se3Matrix<T>, screws: OMatrix<T, U6, N>, frame: ScrewFrame) -> Self {
+        SerialChain {
+            home,
+            screws,
+            frame,
+        }
+    }
+
+    // For a fixed size chain the number of twists must match N
+    pub fn from_twists(home: Se3Matrix<T>, twists: &[Twist<T>], frame: ScrewFrame) -> Self {
+        if let Some(n) = N::try_to_usize() {
+            assert_eq!(
+                twists.len(),
+                n,
+                "SerialChain::from_twists: expected {} twists for a fixed size chain, got {}",
+                n,
+                twists.len()
+            );
+        }
+        let screws =
+            OMatrix::from_fn_generic(U6, N::from_usize(twists.len()), |r, c| twists[c].0[r]);
+        SerialChain::new(home, screws, frame)
+    }
+
+    pub fn dof(&self) -> usize {
+        self.screws.ncols()
+    }
+
+    pub fn screw(&self, i: usize) -> Twist<T> {
+        let s: Vector6<T> = self.screws.column(i).into_owned();
+        s.to_twist()
+    }
+
+    pub fn to_space(&self) -> Self {
+        match self.frame {
+            ScrewFrame::Space => self.clone(),
+            ScrewFrame::Body => self.with_screws(self.home.adjoint(), ScrewFrame::Space),
+        }
+    }
+
+    pub fn to_body(&self) -> Self {
+        match self.frame {
+            ScrewFrame::Body => self.clone(),
+            ScrewFrame::Space => self.with_screws(self.home.inv().adjoint(), ScrewFrame::Body),
+        }
+    }
+
+    pub fn fkin(&self, theta: &OVector<T, N>) -> Se3Matrix<T> {
+        match self.frame {
+            ScrewFrame::Space => fkin_space(&self.home, &self.screws, theta),
+            ScrewFrame::Body => fkin_body(&self.home, &self.screws, theta),
+        }
+    }
+
+    fn with_screws(&self, adjoint: Matrix6<T>, frame: ScrewFrame) -> Self {
+        let (_, ncols) = self.screws.shape_generic();
+        let mut screws = OMatrix::zeros_generic(U6, ncols);
+        for i in 0..self.dof() {
+            screws
+                .column_mut(i)
+                .copy_from(&(adjoint * self.screws.column(i)));
+        }
+        SerialChain::new(self.home, screws, frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::near_zero;
+    use crate::kinematics::fixtures::{
+        textbook_blist, textbook_home, textbook_slist, textbook_target,
+    };
+    use na::{Dyn, Vector3};
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn fkin_body_textbook() {
+        let theta = Vector3::new(FRAC_PI_2, 3.0, PI);
+        let res = fkin_body(&textbook_home(), &textbook_blist(), &theta);
+        assert!(near_zero((res.0 - textbook_target().0).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn fkin_space_textbook() {
+        let theta = Vector3::new(FRAC_PI_2, 3.0, PI);
+        let res = fkin_space(&textbook_home(), &textbook_slist(), &theta);
+        assert!(near_zero((res.0 - textbook_target().0).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn chain_fkin() {
+        let theta = Vector3::new(FRAC_PI_2, 3.0, PI);
+        let body = SerialChain::new(textbook_home(), textbook_blist(), ScrewFrame::Body);
+        let space = SerialChain::new(textbook_home(), textbook_slist(), ScrewFrame::Space);
+        assert!(near_zero(
+            (body.fkin(&theta).0 - textbook_target().0).norm(),
+            Some(1e-6)
+        ));
+        assert!(near_zero(
+            (space.fkin(&theta).0 - textbook_target().0).norm(),
+            Some(1e-6)
+        ));
+    }
+
+    #[test]
+    fn chain_frame_conversion() {
+        let body = SerialChain::new(textbook_home(), textbook_blist(), ScrewFrame::Body);
+        let space = SerialChain::new(textbook_home(), textbook_slist(), ScrewFrame::Space);
+        assert_eq!(body.to_space().frame, ScrewFrame::Space);
+        assert!(near_zero(
+            (body.to_space().screws - textbook_slist()).norm(),
+            Some(1e-9)
+        ));
+        assert!(near_zero(
+            (space.to_body().screws - textbook_blist()).norm(),
+            Some(1e-9)
+        ));
+    }
+
+    #[test]
+    fn chain_dynamic_dof() {
+        let twists = [
+            [0.0, 0.0, 1.0, 4.0, 0.0, 0.0].to_twist(),
+            [0.0, 0.0, 0.0, 0.0, 1.0, 0.0].to_twist(),
+            [0.0, 0.0, -1.0, -6.0, 0.0, -0.1].to_twist(),
+        ];
+        let chain: SerialChain<f64, Dyn> =
+            SerialChain::from_twists(textbook_home(), &twists, ScrewFrame::Space);
+        assert_eq!(chain.dof(), 3);
+        assert_eq!(chain.screw(2), twists[2]);
+        let theta = na::DVector::from_vec(vec![FRAC_PI_2, 3.0, PI]);
+        assert!(near_zero(
+            (chain.fkin(&theta).0 - textbook_target().0).norm(),
+            Some(1e-6)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 3 twists")]
+    fn chain_from_twists_length_mismatch() {
+        let twists = [[0.0, 0.0, 1.0, 4.0, 0.0, 0.0].to_twist()];
+        let _: SerialChain<f64, na::U3> =
+            SerialChain::from_twists(textbook_home(), &twists, ScrewFrame::Space);
+    }
+}
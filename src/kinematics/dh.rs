@@ -0,0 +1,257 @@
+extern crate nalgebra as na;
+use crate::groups::{Se3Matrix, ToSe3, Twist};
+use crate::interfaces::Numeric;
+use crate::kinematics::{ScrewFrame, SerialChain};
+use na::{DVector, Dyn, Matrix4, RealField, Vector3, Vector6};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DhConvention {
+    // Rot_z(theta) Trans_z(d) Trans_x(a) Rot_x(alpha), joint i moves about z_(i-1)
+    Standard,
+    // Rot_x(alpha) Trans_x(a) Rot_z(theta) Trans_z(d), joint i moves about z_i (Craig)
+    Modified,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DhJoint {
+    Revolute,
+    Prismatic,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DhLink<T: Numeric<T>> {
+    pub a: T,
+    pub alpha: T,
+    pub d: T,
+    pub theta: T,
+    pub joint: DhJoint,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DhTable<T: Numeric<T>> {
+    pub links: Vec<DhLink<T>>,
+    pub convention: DhConvention,
+}
+
+fn rot_z<T: Numeric<T> + RealField>(theta: T) -> Matrix4<T> {
+    let (s, c) = theta.sin_cos();
+    let (o, l) = (T::zero(), T::one());
+    Matrix4::new(c, -s, o, o, s, c, o, o, o, o, l, o, o, o, o, l)
+}
+
+fn rot_x<T: Numeric<T> + RealField>(alpha: T) -> Matrix4<T> {
+    let (s, c) = alpha.sin_cos();
+    let (o, l) = (T::zero(), T::one());
+    Matrix4::new(l, o, o, o, o, c, -s, o, o, s, c, o, o, o, o, l)
+}
+
+fn trans<T: Numeric<T> + RealField>(x: T, z: T) -> Matrix4<T> {
+    let (o, l) = (T::zero(), T::one());
+    Matrix4::new(l, o, o, x, o, l, o, o, o, o, l, z, o, o, o, l)
+}
+
+impl<T: Numeric<T> + RealField> DhLink<T> {
+    pub fn new(a: T, alpha: T, d: T, theta: T, joint: DhJoint) -> Self {
+        DhLink {
+            a,
+            alpha,
+            d,
+            theta,
+            joint,
+        }
+    }
+
+    // theta and d are offsets, the joint variable q is added to the one the joint drives
+    pub fn transform(&self, convention: DhConvention, q: T) -> Se3Matrix<T> {
+        let (theta, d) = match self.joint {
+            DhJoint::Revolute => (self.theta + q, self.d),
+            DhJoint::Prismatic => (self.theta, self.d + q),
+        };
+        match convention {
+            DhConvention::Standard => rot_z(theta) * trans(self.a, d) * rot_x(self.alpha),
+            DhConvention::Modified => rot_x(self.alpha) * trans(self.a, d) * rot_z(theta),
+        }
+        .to_se3()
+    }
+}
+
+impl<T: Numeric<T> + RealField> DhTable<T>
+where
+    f64: From<T>,
+{
+    pub fn new(links: Vec<DhLink<T>>, convention: DhConvention) -> Self {
+        DhTable { links, convention }
+    }
+
+    pub fn dof(&self) -> usize {
+        self.links.len()
+    }
+
+    // T_(i-1),i for every link, q must hold one value per link
+    pub fn link_transforms(&self, q: &DVector<T>) -> Vec<Se3Matrix<T>> {
+        assert_eq!(
+            q.len(),
+            self.dof(),
+            "DhTable: expected {} joint values, got {}",
+            self.dof(),
+            q.len()
+        );
+        self.links
+            .iter()
+            .zip(q.iter())
+            .map(|(link, &qi)| link.transform(self.convention, qi))
+            .collect()
+    }
+
+    pub fn fkin(&self, q: &DVector<T>) -> Se3Matrix<T> {
+        self.link_transforms(q)
+            .into_iter()
+            .fold(Matrix4::identity().to_se3(), |t, link| t * link)
+    }
+
+    // Home configuration M and space frame screw axes of the same chain
+    pub fn to_serial_chain(&self) -> SerialChain<T, Dyn> {
+        let zero = self.link_transforms(&DVector::zeros(self.dof()));
+        let mut frame = Matrix4::identity().to_se3();
+        let mut twists = Vec::with_capacity(self.dof());
+        for (link, t) in self.links.iter().zip(zero) {
+            if self.convention == DhConvention::Modified {
+                frame = frame * t;
+            }
+            let z = frame.0.fixed_view::<3, 1>(0, 2).clone_owned();
+            let p = frame.0.fixed_view::<3, 1>(0, 3).clone_owned();
+            let (omega, v) = match link.joint {
+                DhJoint::Revolute => (z, -z.cross(&p)),
+                DhJoint::Prismatic => (Vector3::zeros(), z),
+            };
+            twists.push(Twist(Vector6::new(
+                omega[0], omega[1], omega[2], v[0], v[1], v[2],
+            )));
+            if self.convention == DhConvention::Standard {
+                frame = frame * t;
+            }
+        }
+        SerialChain::from_twists(frame, &twists, ScrewFrame::Space)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::near_zero;
+    use std::f64::consts::FRAC_PI_2;
+
+    // PUMA 560 with Craig's modified parameters
+    fn puma_modified() -> DhTable<f64> {
+        let r = DhJoint::Revolute;
+        DhTable::new(
+            vec![
+                DhLink::new(0.0, 0.0, 0.0, 0.0, r),
+                DhLink::new(0.0, -FRAC_PI_2, 0.0, 0.0, r),
+                DhLink::new(0.4318, 0.0, 0.15, 0.0, r),
+                DhLink::new(0.0203, -FRAC_PI_2, 0.4318, 0.0, r),
+                DhLink::new(0.0, FRAC_PI_2, 0.0, 0.0, r),
+                DhLink::new(0.0, -FRAC_PI_2, 0.0, 0.0, r),
+            ],
+            DhConvention::Modified,
+        )
+    }
+
+    // UR5 with standard parameters
+    fn ur5_standard() -> DhTable<f64> {
+        let r = DhJoint::Revolute;
+        DhTable::new(
+            vec![
+                DhLink::new(0.0, FRAC_PI_2, 0.089159, 0.0, r),
+                DhLink::new(-0.425, 0.0, 0.0, 0.0, r),
+                DhLink::new(-0.39225, 0.0, 0.0, 0.0, r),
+                DhLink::new(0.0, FRAC_PI_2, 0.10915, 0.0, r),
+                DhLink::new(0.0, -FRAC_PI_2, 0.09465, 0.0, r),
+                DhLink::new(0.0, 0.0, 0.0823, 0.0, r),
+            ],
+            DhConvention::Standard,
+        )
+    }
+
+    fn q() -> DVector<f64> {
+        DVector::from_vec(vec![0.3, -0.8, 1.2, -0.5, 0.7, 0.4])
+    }
+
+    #[test]
+    fn dh_link_transform() {
+        let link = DhLink::new(1.0, FRAC_PI_2, 0.5, 0.0, DhJoint::Revolute);
+        let t = link.transform(DhConvention::Standard, FRAC_PI_2);
+        let expected = Matrix4::new(
+            0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.5, 0.0, 0.0, 0.0, 1.0,
+        );
+        assert!(near_zero((t.0 - expected).norm(), Some(1e-9)));
+        let t = link.transform(DhConvention::Modified, FRAC_PI_2);
+        let expected = Matrix4::new(
+            0.0, -1.0, 0.0, 1.0, 0.0, 0.0, -1.0, -0.5, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        );
+        assert!(near_zero((t.0 - expected).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn dh_standard_matches_poe() {
+        let table = ur5_standard();
+        let chain = table.to_serial_chain();
+        assert!(near_zero(
+            (chain.fkin(&q()).0 - table.fkin(&q()).0).norm(),
+            Some(1e-9)
+        ));
+        assert!(near_zero(
+            (chain.to_body().fkin(&q()).0 - table.fkin(&q()).0).norm(),
+            Some(1e-9)
+        ));
+    }
+
+    #[test]
+    fn dh_modified_matches_poe() {
+        let table = puma_modified();
+        let chain = table.to_serial_chain();
+        assert!(near_zero(
+            (chain.home.0 - table.fkin(&DVector::zeros(6)).0).norm(),
+            Some(1e-9)
+        ));
+        assert!(near_zero(
+            (chain.fkin(&q()).0 - table.fkin(&q()).0).norm(),
+            Some(1e-9)
+        ));
+    }
+
+    #[test]
+    fn dh_prismatic_and_offsets() {
+        for convention in [DhConvention::Standard, DhConvention::Modified] {
+            let table = DhTable::new(
+                vec![
+                    DhLink::new(0.2, FRAC_PI_2, 0.1, 0.3, DhJoint::Revolute),
+                    DhLink::new(0.0, -FRAC_PI_2, 0.5, FRAC_PI_2, DhJoint::Prismatic),
+                    DhLink::new(0.3, 0.0, 0.0, -0.2, DhJoint::Revolute),
+                ],
+                convention,
+            );
+            let chain = table.to_serial_chain();
+            let q = DVector::from_vec(vec![0.4, 0.25, -0.9]);
+            assert!(near_zero(
+                (chain.fkin(&q).0 - table.fkin(&q).0).norm(),
+                Some(1e-9)
+            ));
+        }
+    }
+
+    #[test]
+    fn dh_link_transforms_compose() {
+        let table = puma_modified();
+        let links = table.link_transforms(&q());
+        assert_eq!(links.len(), 6);
+        let t = links.iter().fold(Matrix4::identity(), |t, l| t * l.0);
+        assert!(near_zero((t - table.fkin(&q()).0).norm(), Some(1e-12)));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 6 joint values, got 5")]
+    fn dh_joint_count_mismatch() {
+        puma_modified().fkin(&DVector::zeros(5));
+    }
+}
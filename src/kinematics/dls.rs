@@ -0,0 +1,379 @@
+extern crate nalgebra as na;
+use crate::groups::{Inverse, MatrixLog, Se3Matrix, ToTwist, Twist};
+use crate::interfaces::Numeric;
+use crate::kinematics::ik::twist_errors;
+use crate::kinematics::{IkIteration, IkResult, SerialChain};
+use na::allocator::Allocator;
+use na::{DMatrix, DVector, DefaultAllocator, Dim, OVector, RealField, Vector6, U1, U6};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DlsOptions<T: Numeric<T>, N: Dim>
+where
+    DefaultAllocator: Allocator<T, N>,
+{
+    pub eomg: T,
+    pub ev: T,
+    pub max_iterations: usize,
+    pub damping: T,
+    pub min_damping: T,
+    pub max_damping: T,
+    // Damping is multiplied by damping_increase after a rejected step and damping_decrease after an accepted one
+    pub damping_increase: T,
+    pub damping_decrease: T,
+    // Scales the angular and linear components of the error twist
+    pub weights: Vector6<T>,
+    // Lower and upper joint limits, iterates are clamped into this box, set by with_joint_limits
+    joint_limits: Option<(OVector<T, N>, OVector<T, N>)>,
+    // Posture pulled towards in the null space of the task Jacobian
+    pub rest_posture: Option<OVector<T, N>>,
+    pub null_space_gain: T,
+}
+
+impl<T: Numeric<T> + RealField, N: Dim> DlsOptions<T, N>
+where
+    DefaultAllocator: Allocator<T, N>,
+{
+    pub fn new(eomg: T, ev: T, max_iterations: usize) -> Self {
+        let one = T::one();
+        DlsOptions {
+            eomg,
+            ev,
+            max_iterations,
+            damping: na::convert(1e-2),
+            min_damping: na::convert(1e-9),
+            max_damping: na::convert(1e6),
+            damping_increase: na::convert(10.0),
+            damping_decrease: na::convert(0.1),
+            weights: Vector6::new(one, one, one, one, one, one),
+            joint_limits: None,
+            rest_posture: None,
+            null_space_gain: T::zero(),
+        }
+    }
+
+    // None unless both bounds have the same length and lower[i] <= upper[i] for every joint
+    pub fn with_joint_limits(mut self, lower: OVector<T, N>, upper: OVector<T, N>) -> Option<Self> {
+        if lower.len() != upper.len() || !lower.iter().zip(upper.iter()).all(|(l, u)| l <= u) {
+            return None;
+        }
+        self.joint_limits = Some((lower, upper));
+        Some(self)
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn joint_limits(&self) -> Option<&(OVector<T, N>, OVector<T, N>)> {
+        self.joint_limits.as_ref()
+    }
+
+    fn clamp(&self, theta: &mut OVector<T, N>) {
+        if let Some((lower, upper)) = &self.joint_limits {
+            for i in 0..theta.len() {
+                theta[i] = theta[i].clamp(lower[i], upper[i]);
+            }
+        }
+    }
+}
+
+impl<T: Numeric<T> + RealField, N: Dim> SerialChain<T, N>
+where
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    // Body frame error twist V_b with [V_b] = log(T_sb^-1 T_sd)
+    pub fn pose_error(&self, t_sd: &Se3Matrix<T>, theta: &OVector<T, N>) -> Twist<T> {
+        (self.fkin(theta).inv() * *t_sd).log().to_twist()
+    }
+
+    // Success compares the weighted error with eomg and ev, the reported errors are unweighted
+    pub fn ikin_dls(
+        &self,
+        t_sd: &Se3Matrix<T>,
+        theta0: &OVector<T, N>,
+        options: &DlsOptions<T, N>,
+    ) -> IkResult<T, N> {
+        let n = theta0.len();
+        if let Some((lower, _)) = options.joint_limits() {
+            assert_eq!(
+                lower.len(),
+                n,
+                "ikin_dls: expected {} joint limits, got {}",
+                n,
+                lower.len()
+            );
+        }
+        if let Some(rest) = &options.rest_posture {
+            assert_eq!(
+                rest.len(),
+                n,
+                "ikin_dls: expected {} rest posture values, got {}",
+                n,
+                rest.len()
+            );
+        }
+        let (ndim, _) = theta0.shape_generic();
+        let weights = options.weights;
+        // Weighted error for the step and the convergence test, unweighted norms for reporting
+        let weighted_error = |theta: &OVector<T, N>| {
+            let raw = self.pose_error(t_sd, theta).0;
+            let e = raw.component_mul(&weights);
+            let (omega_weighted, v_weighted) = twist_errors(&e);
+            let converged = omega_weighted <= options.eomg && v_weighted <= options.ev;
+            let (omega_error, v_error) = twist_errors(&raw);
+            (e, omega_error, v_error, converged)
+        };
+
+        let mut theta = theta0.clone_owned();
+        options.clamp(&mut theta);
+        let mut damping = options.damping;
+        let (mut e, mut omega_error, mut v_error, mut success) = weighted_error(&theta);
+        let mut trace = vec![IkIteration {
+            theta: theta.clone(),
+            omega_error,
+            v_error,
+        }];
+        let mut iterations = 0;
+        while !success && iterations < options.max_iterations {
+            iterations += 1;
+            let jb = self.jacobian_body(&theta);
+            let mut j = DMatrix::from_column_slice(6, n, jb.as_slice());
+            for (r, w) in weights.iter().enumerate() {
+                j.row_mut(r).scale_mut(*w);
+            }
+            let jt = j.transpose();
+            let damped = &jt * &j + DMatrix::identity(n, n) * damping;
+            // A failed factorisation counts as a rejected step
+            let mut accepted = false;
+            if let Some(chol) = damped.cholesky() {
+                let mut dtheta = chol.solve(&jt) * DVector::from_column_slice(e.as_slice());
+                if let Some(rest) = &options.rest_posture {
+                    let pull = DVector::from_iterator(
+                        n,
+                        (0..n).map(|i| (rest[i] - theta[i]) * options.null_space_gain),
+                    );
+                    // Project with the undamped pseudo-inverse so the pull leaves the task unchanged
+                    if let Ok(j_pinv) = j.clone().pseudo_inverse(na::convert(1e-12)) {
+                        let projector = DMatrix::identity(n, n) - j_pinv * &j;
+                        dtheta += projector * pull;
+                    }
+                }
+
+                let mut candidate =
+                    &theta + OVector::from_column_slice_generic(ndim, U1, dtheta.as_slice());
+                options.clamp(&mut candidate);
+                let (e_new, omega_new, v_new, converged) = weighted_error(&candidate);
+                if e_new.norm_squared() < e.norm_squared() {
+                    theta = candidate;
+                    e = e_new;
+                    omega_error = omega_new;
+                    v_error = v_new;
+                    success = converged;
+                    accepted = true;
+                }
+            }
+            damping = if accepted {
+                (damping * options.damping_decrease).max(options.min_damping)
+            } else {
+                (damping * options.damping_increase).min(options.max_damping)
+            };
+            // Rejected steps repeat the current configuration so that trace.len() == iterations + 1
+            trace.push(IkIteration {
+                theta: theta.clone(),
+                omega_error,
+                v_error,
+            });
+        }
+        IkResult {
+            theta,
+            success,
+            iterations,
+            omega_error,
+            v_error,
+            trace,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::{ToRP, ToSe3};
+    use crate::helpers::near_zero;
+    use crate::kinematics::fixtures::{textbook_blist, textbook_home, textbook_t_sd};
+    use crate::kinematics::ScrewFrame;
+    use na::{Matrix4, Matrix6x2, Matrix6x3, Vector2, Vector3};
+
+    fn planar_2r() -> SerialChain<f64, na::Const<2>> {
+        let home = Matrix4::new(
+            1.0, 0.0, 0.0, 2.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3();
+        let slist = Matrix6x2::from_columns(&[
+            Vector6::new(0.0, 0.0, 1.0, 0.0, 0.0, 0.0),
+            Vector6::new(0.0, 0.0, 1.0, 0.0, -1.0, 0.0),
+        ]);
+        SerialChain::new(home, slist, ScrewFrame::Space)
+    }
+
+    fn planar_3r() -> SerialChain<f64, na::Const<3>> {
+        let home = Matrix4::new(
+            1.0, 0.0, 0.0, 3.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3();
+        let slist = Matrix6x3::from_columns(&[
+            Vector6::new(0.0, 0.0, 1.0, 0.0, 0.0, 0.0),
+            Vector6::new(0.0, 0.0, 1.0, 0.0, -1.0, 0.0),
+            Vector6::new(0.0, 0.0, 1.0, 0.0, -2.0, 0.0),
+        ]);
+        SerialChain::new(home, slist, ScrewFrame::Space)
+    }
+
+    fn target(x: f64, y: f64, yaw: f64) -> Se3Matrix<f64> {
+        let (s, c) = yaw.sin_cos();
+        Matrix4::new(
+            c, -s, 0.0, x, s, c, 0.0, y, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3()
+    }
+
+    #[test]
+    fn dls_textbook() {
+        let t_sd = textbook_t_sd();
+        let chain = SerialChain::new(textbook_home(), textbook_blist(), ScrewFrame::Body);
+        let options = DlsOptions::new(0.001, 0.0001, 100);
+        let res = chain.ikin_dls(&t_sd, &Vector3::new(1.5, 2.5, 3.0), &options);
+        assert!(res.success);
+        assert!(near_zero(
+            (chain.fkin(&res.theta).0 - t_sd.0).norm(),
+            Some(1e-3)
+        ));
+    }
+
+    #[test]
+    fn dls_pose_error_is_body_twist() {
+        let chain = planar_2r();
+        let theta = Vector2::new(0.3, 0.4);
+        let err = chain.pose_error(&chain.fkin(&theta), &theta);
+        assert!(near_zero(err.0.norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn dls_position_only_weighting() {
+        let chain = planar_2r();
+        // Position (1, 1) is reachable but not with this orientation
+        let t_sd = target(1.0, 1.0, 1.0);
+        let theta0 = Vector2::new(0.2, 0.5);
+        let full = chain.ikin_dls(&t_sd, &theta0, &DlsOptions::new(1e-4, 1e-4, 50));
+        assert!(!full.success);
+        let mut options = DlsOptions::new(1e-4, 1e-4, 50);
+        options.weights = Vector6::new(0.0, 0.0, 0.0, 1.0, 1.0, 1.0);
+        let position_only = chain.ikin_dls(&t_sd, &theta0, &options);
+        assert!(position_only.success);
+        // Errors are reported unweighted, so the ignored orientation error still shows
+        assert!(position_only.omega_error > 0.1);
+        assert!(position_only.v_error <= 1e-4);
+        let (_, p) = chain.fkin(&position_only.theta).to_rp();
+        assert!(near_zero(
+            (p - Vector3::new(1.0, 1.0, 0.0)).norm(),
+            Some(1e-3)
+        ));
+    }
+
+    #[test]
+    fn dls_out_of_reach_stays_bounded() {
+        let chain = planar_2r();
+        let mut options = DlsOptions::new(1e-4, 1e-4, 100);
+        options.weights = Vector6::new(0.0, 0.0, 0.0, 1.0, 1.0, 1.0);
+        let res = chain.ikin_dls(&target(3.0, 0.0, 0.0), &Vector2::new(0.1, 0.1), &options);
+        assert!(!res.success);
+        assert!(res.theta.iter().all(|t| t.is_finite() && t.abs() < 1.0));
+        let (_, p) = chain.fkin(&res.theta).to_rp();
+        assert!(near_zero(
+            (p - Vector3::new(2.0, 0.0, 0.0)).norm(),
+            Some(1e-2)
+        ));
+    }
+
+    #[test]
+    fn dls_joint_limits() {
+        let chain = planar_2r();
+        let mut options = DlsOptions::new(1e-4, 1e-4, 100);
+        options.weights = Vector6::new(0.0, 0.0, 0.0, 1.0, 1.0, 1.0);
+        // Only the elbow-down solution theta = (pi/2, -pi/2) satisfies the limits
+        let options = options
+            .with_joint_limits(Vector2::new(-3.0, -3.0), Vector2::new(3.0, 0.0))
+            .unwrap();
+        let res = chain.ikin_dls(&target(1.0, 1.0, 0.0), &Vector2::new(1.0, -0.3), &options);
+        assert!(res.success);
+        assert!(res.theta[1] <= 0.0);
+        assert!(res
+            .trace
+            .iter()
+            .all(|it| it.theta[1] <= 0.0 && it.theta[0] >= -3.0));
+    }
+
+    #[test]
+    fn dls_null_space_objective() {
+        let chain = planar_3r();
+        let rest = Vector3::new(1.0, -0.5, -0.5);
+        let theta0 = Vector3::new(0.0, 0.0, 0.0);
+        let t_sd = target(1.5, 1.0, 0.0);
+        let mut options = DlsOptions::new(1e-6, 1e-6, 200);
+        options.weights = Vector6::new(0.0, 0.0, 0.0, 1.0, 1.0, 1.0);
+        let plain = chain.ikin_dls(&t_sd, &theta0, &options);
+        options.rest_posture = Some(rest);
+        options.null_space_gain = 0.5;
+        let pulled = chain.ikin_dls(&t_sd, &theta0, &options);
+        assert!(plain.success && pulled.success);
+        assert!((pulled.theta - rest).norm() < (plain.theta - rest).norm());
+    }
+
+    #[test]
+    fn dls_trace_covers_every_iteration() {
+        let chain = planar_2r();
+        let mut options = DlsOptions::new(1e-4, 1e-4, 30);
+        options.weights = Vector6::new(0.0, 0.0, 0.0, 1.0, 1.0, 1.0);
+        // Out of reach, so some steps are rejected before the iteration limit
+        let res = chain.ikin_dls(&target(3.0, 0.0, 0.0), &Vector2::new(0.1, 0.1), &options);
+        assert_eq!(res.iterations, 30);
+        assert_eq!(res.trace.len(), res.iterations + 1);
+        assert_eq!(res.trace.last().unwrap().theta, res.theta);
+        let res = chain.ikin_dls(&target(1.0, 1.0, 0.0), &Vector2::new(1.0, -0.3), &options);
+        assert!(res.success);
+        assert_eq!(res.trace.len(), res.iterations + 1);
+    }
+
+    #[test]
+    fn dls_invalid_joint_limits() {
+        let options = DlsOptions::<f64, na::U2>::new(1e-4, 1e-4, 10);
+        let inverted = Vector2::new(1.0, 1.0);
+        assert!(options
+            .clone()
+            .with_joint_limits(inverted, Vector2::new(2.0, 0.0))
+            .is_none());
+        assert!(options
+            .clone()
+            .with_joint_limits(Vector2::new(f64::NAN, 0.0), Vector2::new(1.0, 1.0))
+            .is_none());
+        let dynamic = DlsOptions::<f64, na::Dyn>::new(1e-4, 1e-4, 10);
+        assert!(dynamic
+            .with_joint_limits(na::DVector::zeros(2), na::DVector::zeros(3))
+            .is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 2 joint limits, got 3")]
+    fn dls_joint_limit_count_mismatch() {
+        let chain: SerialChain<f64, na::Dyn> = SerialChain::new(
+            planar_2r().home,
+            na::Matrix6xX::from_column_slice(planar_2r().screws.as_slice()),
+            ScrewFrame::Space,
+        );
+        let options = DlsOptions::new(1e-4, 1e-4, 10)
+            .with_joint_limits(
+                na::DVector::from_element(3, -1.0),
+                na::DVector::from_element(3, 1.0),
+            )
+            .unwrap();
+        chain.ikin_dls(&target(1.0, 1.0, 0.0), &na::DVector::zeros(2), &options);
+    }
+}
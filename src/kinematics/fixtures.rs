@@ -0,0 +1,44 @@
+// Textbook arm shared by the kinematics tests
+extern crate nalgebra as na;
+use crate::groups::{Se3Matrix, ToSe3};
+use na::{Matrix4, Matrix6x3, Vector6};
+
+// Three joint RRP arm from the chapter 4 and 6 examples
+pub(crate) fn textbook_home() -> Se3Matrix<f64> {
+    Matrix4::new(
+        -1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 6.0, 0.0, 0.0, -1.0, 2.0, 0.0, 0.0, 0.0, 1.0,
+    )
+    .to_se3()
+}
+
+pub(crate) fn textbook_blist() -> Matrix6x3<f64> {
+    Matrix6x3::from_columns(&[
+        Vector6::new(0.0, 0.0, -1.0, 2.0, 0.0, 0.0),
+        Vector6::new(0.0, 0.0, 0.0, 0.0, 1.0, 0.0),
+        Vector6::new(0.0, 0.0, 1.0, 0.0, 0.0, 0.1),
+    ])
+}
+
+pub(crate) fn textbook_slist() -> Matrix6x3<f64> {
+    Matrix6x3::from_columns(&[
+        Vector6::new(0.0, 0.0, 1.0, 4.0, 0.0, 0.0),
+        Vector6::new(0.0, 0.0, 0.0, 0.0, 1.0, 0.0),
+        Vector6::new(0.0, 0.0, -1.0, -6.0, 0.0, -0.1),
+    ])
+}
+
+// End-effector pose at theta = (pi/2, 3, pi)
+pub(crate) fn textbook_target() -> Se3Matrix<f64> {
+    Matrix4::new(
+        0.0, 1.0, 0.0, -5.0, 1.0, 0.0, 0.0, 4.0, 0.0, 0.0, -1.0, 1.68584073, 0.0, 0.0, 0.0, 1.0,
+    )
+    .to_se3()
+}
+
+// Target of the inverse kinematics examples, textbook_target rounded to four places
+pub(crate) fn textbook_t_sd() -> Se3Matrix<f64> {
+    Matrix4::new(
+        0.0, 1.0, 0.0, -5.0, 1.0, 0.0, 0.0, 4.0, 0.0, 0.0, -1.0, 1.6858, 0.0, 0.0, 0.0, 1.0,
+    )
+    .to_se3()
+}
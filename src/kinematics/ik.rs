@@ -0,0 +1,262 @@
+extern crate nalgebra as na;
+use crate::groups::{Adjoint, Inverse, MatrixLog, Se3Matrix, ToVec};
+use crate::interfaces::Numeric;
+use crate::kinematics::{
+    fkin_body, fkin_space, jacobian_body, jacobian_space, ScrewFrame, SerialChain,
+};
+use na::allocator::Allocator;
+use na::{DMatrix, DVector, DefaultAllocator, Dim, OMatrix, OVector, RealField, Vector6, U1, U6};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IkIteration<T: Numeric<T>, N: Dim>
+where
+    DefaultAllocator: Allocator<T, N>,
+{
+    pub theta: OVector<T, N>,
+    pub omega_error: T,
+    pub v_error: T,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IkResult<T: Numeric<T>, N: Dim>
+where
+    DefaultAllocator: Allocator<T, N>,
+{
+    pub theta: OVector<T, N>,
+    pub success: bool,
+    pub iterations: usize,
+    pub omega_error: T,
+    pub v_error: T,
+    // One entry per evaluated configuration, starting with the initial guess
+    pub trace: Vec<IkIteration<T, N>>,
+}
+
+// Angular and linear magnitudes of an error twist
+pub(crate) fn twist_errors<T: Numeric<T> + RealField>(v: &Vector6<T>) -> (T, T) {
+    (
+        v.fixed_view::<3, 1>(0, 0).norm(),
+        v.fixed_view::<3, 1>(3, 0).norm(),
+    )
+}
+
+// The pseudo-inverse is taken on a dynamically sized copy so the SVD bounds stay out of the API
+fn pinv_solve<T, N>(jacobian: &OMatrix<T, U6, N>, v: &Vector6<T>) -> OVector<T, N>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    let (_, ncols) = jacobian.shape_generic();
+    let j = DMatrix::from_column_slice(6, jacobian.ncols(), jacobian.as_slice());
+    let pinv = j
+        .pseudo_inverse(na::convert(1e-12))
+        .expect("pseudo-inverse epsilon is non-negative");
+    let dtheta = pinv * DVector::from_column_slice(v.as_slice());
+    OVector::from_column_slice_generic(ncols, U1, dtheta.as_slice())
+}
+
+fn newton_raphson<T, N, F>(
+    theta0: &OVector<T, N>,
+    eomg: T,
+    ev: T,
+    max_iterations: usize,
+    error_and_jacobian: F,
+) -> IkResult<T, N>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+    F: Fn(&OVector<T, N>) -> (Vector6<T>, OMatrix<T, U6, N>),
+{
+    let mut theta = theta0.clone_owned();
+    let mut trace = Vec::new();
+    let mut iterations = 0;
+    loop {
+        let (v, jacobian) = error_and_jacobian(&theta);
+        let (omega_error, v_error) = twist_errors(&v);
+        trace.push(IkIteration {
+            theta: theta.clone(),
+            omega_error,
+            v_error,
+        });
+        let success = omega_error <= eomg && v_error <= ev;
+        if success || iterations >= max_iterations {
+            return IkResult {
+                theta,
+                success,
+                iterations,
+                omega_error,
+                v_error,
+                trace,
+            };
+        }
+        theta += pinv_solve(&jacobian, &v);
+        iterations += 1;
+    }
+}
+
+pub fn ikin_body<T, N>(
+    blist: &OMatrix<T, U6, N>,
+    home: &Se3Matrix<T>,
+    t_sd: &Se3Matrix<T>,
+    theta0: &OVector<T, N>,
+    eomg: T,
+    ev: T,
+    max_iterations: usize,
+) -> IkResult<T, N>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    newton_raphson(theta0, eomg, ev, max_iterations, |theta| {
+        let t_bd = fkin_body(home, blist, theta).inv() * *t_sd;
+        (t_bd.log().to_vec(), jacobian_body(blist, theta))
+    })
+}
+
+pub fn ikin_space<T, N>(
+    slist: &OMatrix<T, U6, N>,
+    home: &Se3Matrix<T>,
+    t_sd: &Se3Matrix<T>,
+    theta0: &OVector<T, N>,
+    eomg: T,
+    ev: T,
+    max_iterations: usize,
+) -> IkResult<T, N>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    newton_raphson(theta0, eomg, ev, max_iterations, |theta| {
+        let t_sb = fkin_space(home, slist, theta);
+        let vs = t_sb.adjoint() * (t_sb.inv() * *t_sd).log().to_vec();
+        (vs, jacobian_space(slist, theta))
+    })
+}
+
+impl<T: Numeric<T> + RealField, N: Dim> SerialChain<T, N>
+where
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    pub fn ikin(
+        &self,
+        t_sd: &Se3Matrix<T>,
+        theta0: &OVector<T, N>,
+        eomg: T,
+        ev: T,
+        max_iterations: usize,
+    ) -> IkResult<T, N> {
+        match self.frame {
+            ScrewFrame::Space => ikin_space(
+                &self.screws,
+                &self.home,
+                t_sd,
+                theta0,
+                eomg,
+                ev,
+                max_iterations,
+            ),
+            ScrewFrame::Body => ikin_body(
+                &self.screws,
+                &self.home,
+                t_sd,
+                theta0,
+                eomg,
+                ev,
+                max_iterations,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::ToSe3;
+    use crate::helpers::near_zero;
+    use crate::kinematics::fixtures::{
+        textbook_blist, textbook_home, textbook_slist, textbook_t_sd,
+    };
+    use na::{Matrix4, Vector3};
+
+    #[test]
+    fn ikin_body_textbook() {
+        let theta0 = Vector3::new(1.5, 2.5, 3.0);
+        let res = ikin_body(
+            &textbook_blist(),
+            &textbook_home(),
+            &textbook_t_sd(),
+            &theta0,
+            0.01,
+            0.001,
+            20,
+        );
+        let tar = Vector3::new(1.57073819, 2.999667, 3.14153913);
+        assert!(res.success);
+        assert!(near_zero((res.theta - tar).norm(), Some(1e-6)));
+        assert_eq!(res.trace.len(), res.iterations + 1);
+        assert_eq!(res.trace[0].theta, theta0);
+        assert!(res.omega_error <= 0.01 && res.v_error <= 0.001);
+    }
+
+    #[test]
+    fn ikin_space_textbook() {
+        let theta0 = Vector3::new(1.5, 2.5, 3.0);
+        let res = ikin_space(
+            &textbook_slist(),
+            &textbook_home(),
+            &textbook_t_sd(),
+            &theta0,
+            0.01,
+            0.001,
+            20,
+        );
+        let tar = Vector3::new(1.57073783, 2.99966384, 3.1415342);
+        assert!(res.success);
+        assert!(near_zero((res.theta - tar).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn ikin_reports_failure() {
+        // Out of reach for the arm, the solver runs out of iterations
+        let unreachable = Matrix4::new(
+            1.0, 0.0, 0.0, 50.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3();
+        let theta0 = Vector3::new(1.5, 2.5, 3.0);
+        let res = ikin_body(
+            &textbook_blist(),
+            &textbook_home(),
+            &unreachable,
+            &theta0,
+            0.01,
+            0.001,
+            5,
+        );
+        assert!(!res.success);
+        assert_eq!(res.iterations, 5);
+        assert_eq!(res.trace.len(), 6);
+        assert!(res.v_error > 0.001);
+    }
+
+    #[test]
+    fn chain_ikin() {
+        let theta0 = Vector3::new(1.5, 2.5, 3.0);
+        let chain = SerialChain::new(textbook_home(), textbook_blist(), ScrewFrame::Body);
+        let res = chain.ikin(&textbook_t_sd(), &theta0, 0.01, 0.001, 20);
+        assert!(res.success);
+        assert!(near_zero(
+            (chain.fkin(&res.theta).0 - textbook_t_sd().0).norm(),
+            Some(1e-2)
+        ));
+        let res_space = chain
+            .to_space()
+            .ikin(&textbook_t_sd(), &theta0, 0.01, 0.001, 20);
+        assert!(res_space.success);
+    }
+}
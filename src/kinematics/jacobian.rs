@@ -0,0 +1,197 @@
+extern crate nalgebra as na;
+use crate::groups::{Adjoint, MatrixExp, Se3Matrix, ToSe3, ToTwist};
+use crate::interfaces::Numeric;
+use crate::kinematics::{ScrewFrame, SerialChain};
+use na::allocator::Allocator;
+use na::{DefaultAllocator, Dim, Matrix4, OMatrix, OVector, RealField, Vector6, U6};
+
+pub fn jacobian_space<T, N>(slist: &OMatrix<T, U6, N>, theta: &OVector<T, N>) -> OMatrix<T, U6, N>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    let mut js = slist.clone_owned();
+    let mut t: Se3Matrix<T> = Matrix4::identity().to_se3();
+    for i in 1..theta.len() {
+        let s: Vector6<T> = slist.column(i - 1).into_owned();
+        t = t * (s * theta[i - 1]).to_twist().exp();
+        js.column_mut(i).copy_from(&(t.adjoint() * slist.column(i)));
+    }
+    js
+}
+
+pub fn jacobian_body<T, N>(blist: &OMatrix<T, U6, N>, theta: &OVector<T, N>) -> OMatrix<T, U6, N>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    let mut jb = blist.clone_owned();
+    let mut t: Se3Matrix<T> = Matrix4::identity().to_se3();
+    for i in (0..theta.len().saturating_sub(1)).rev() {
+        let b: Vector6<T> = blist.column(i + 1).into_owned();
+        t = t * (b * -theta[i + 1]).to_twist().exp();
+        jb.column_mut(i).copy_from(&(t.adjoint() * blist.column(i)));
+    }
+    jb
+}
+
+// J_s = [Ad_Tsb] J_b, with T_sb the end-effector pose
+pub fn jacobian_body_to_space<T, N>(
+    jb: &OMatrix<T, U6, N>,
+    t_sb: &Se3Matrix<T>,
+) -> OMatrix<T, U6, N>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    DefaultAllocator: Allocator<T, U6, N>,
+{
+    t_sb.adjoint() * jb
+}
+
+// J_b = [Ad_Tbs] J_s, with T_sb the end-effector pose
+pub fn jacobian_space_to_body<T, N>(
+    js: &OMatrix<T, U6, N>,
+    t_sb: &Se3Matrix<T>,
+) -> OMatrix<T, U6, N>
+where
+    T: Numeric<T> + RealField,
+    N: Dim,
+    DefaultAllocator: Allocator<T, U6, N>,
+{
+    t_sb.adjoint_inv() * js
+}
+
+impl<T: Numeric<T> + RealField, N: Dim> SerialChain<T, N>
+where
+    f64: From<T>,
+    DefaultAllocator: Allocator<T, U6, N> + Allocator<T, N>,
+{
+    pub fn jacobian_space(&self, theta: &OVector<T, N>) -> OMatrix<T, U6, N> {
+        match self.frame {
+            ScrewFrame::Space => jacobian_space(&self.screws, theta),
+            ScrewFrame::Body => {
+                jacobian_body_to_space(&jacobian_body(&self.screws, theta), &self.fkin(theta))
+            }
+        }
+    }
+
+    pub fn jacobian_body(&self, theta: &OVector<T, N>) -> OMatrix<T, U6, N> {
+        match self.frame {
+            ScrewFrame::Body => jacobian_body(&self.screws, theta),
+            ScrewFrame::Space => {
+                jacobian_space_to_body(&jacobian_space(&self.screws, theta), &self.fkin(theta))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::near_zero;
+    use crate::kinematics::fixtures::{textbook_blist, textbook_home};
+    use na::{DVector, Matrix6x4, Matrix6xX, Vector3, Vector4};
+
+    fn screws() -> Matrix6x4<f64> {
+        Matrix6x4::from_columns(&[
+            Vector6::new(0.0, 0.0, 1.0, 0.0, 0.2, 0.2),
+            Vector6::new(1.0, 0.0, 0.0, 2.0, 0.0, 3.0),
+            Vector6::new(0.0, 1.0, 0.0, 0.0, 2.0, 1.0),
+            Vector6::new(1.0, 0.0, 0.0, 0.2, 0.3, 0.4),
+        ])
+    }
+
+    #[test]
+    fn jacobian_body_textbook() {
+        let theta = Vector4::new(0.2, 1.1, 0.1, 1.2);
+        let tar = Matrix6x4::new(
+            -0.04528405,
+            0.99500417,
+            0.0,
+            1.0,
+            0.74359313,
+            0.09304865,
+            0.36235775,
+            0.0,
+            -0.66709716,
+            0.03617541,
+            -0.93203909,
+            0.0,
+            2.32586047,
+            1.66809,
+            0.56410831,
+            0.2,
+            -1.44321167,
+            2.94561275,
+            1.43306521,
+            0.3,
+            -2.06639565,
+            1.82881722,
+            -1.58868628,
+            0.4,
+        );
+        let res = jacobian_body(&screws(), &theta);
+        assert!(near_zero((res - tar).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn jacobian_space_textbook() {
+        let theta = Vector4::new(0.2, 1.1, 0.1, 1.2);
+        let tar = Matrix6x4::new(
+            0.0,
+            0.98006658,
+            -0.09011564,
+            0.95749426,
+            0.0,
+            0.19866933,
+            0.4445544,
+            0.28487557,
+            1.0,
+            0.0,
+            0.89120736,
+            -0.04528405,
+            0.0,
+            1.95218638,
+            -2.21635216,
+            -0.51161537,
+            0.2,
+            0.43654132,
+            -2.43712573,
+            2.77535713,
+            0.2,
+            2.96026613,
+            3.23573065,
+            2.22512443,
+        );
+        let res = jacobian_space(&screws(), &theta);
+        assert!(near_zero((res - tar).norm(), Some(1e-6)));
+    }
+
+    #[test]
+    fn jacobian_dynamic_matches_fixed() {
+        let theta = Vector4::new(0.2, 1.1, 0.1, 1.2);
+        let slist = Matrix6xX::from_column_slice(screws().as_slice());
+        let theta_dyn = DVector::from_column_slice(theta.as_slice());
+        let res = jacobian_space(&slist, &theta_dyn);
+        assert_eq!(res.as_slice(), jacobian_space(&screws(), &theta).as_slice());
+    }
+
+    #[test]
+    fn chain_jacobian_conversion() {
+        let body = SerialChain::new(textbook_home(), textbook_blist(), ScrewFrame::Body);
+        let space = body.to_space();
+        let theta = Vector3::new(0.3, 1.2, -0.5);
+        assert!(near_zero(
+            (body.jacobian_space(&theta) - space.jacobian_space(&theta)).norm(),
+            Some(1e-9)
+        ));
+        assert!(near_zero(
+            (space.jacobian_body(&theta) - body.jacobian_body(&theta)).norm(),
+            Some(1e-9)
+        ));
+    }
+}
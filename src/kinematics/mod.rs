@@ -0,0 +1,15 @@
+mod analytic;
+mod chain;
+mod dh;
+mod dls;
+#[cfg(test)]
+mod fixtures;
+mod ik;
+mod jacobian;
+
+pub use analytic::{AnalyticArm, ArmGeometry};
+pub use chain::{fkin_body, fkin_space, ScrewFrame, SerialChain};
+pub use dh::{DhConvention, DhJoint, DhLink, DhTable};
+pub use dls::DlsOptions;
+pub use ik::{ikin_body, ikin_space, IkIteration, IkResult};
+pub use jacobian::{jacobian_body, jacobian_body_to_space, jacobian_space, jacobian_space_to_body};
@@ -1,6 +1,12 @@
 #[macro_use]
 pub mod concat;
+pub mod dynamics;
+pub mod errors;
 pub mod groups;
 pub mod helpers;
 pub mod interfaces;
+pub mod kinematics;
+pub mod trajectory;
 pub mod transforms;
+#[cfg(feature = "urdf")]
+pub mod urdf;
@@ -0,0 +1,6 @@
+mod time_scaling;
+
+pub use time_scaling::{
+    CubicTimeScaling, QuinticTimeScaling, SCurveTimeScaling, ScalingState, TimeScaling,
+    TrapezoidalTimeScaling,
+};
@@ -0,0 +1,453 @@
+extern crate nalgebra as na;
+use crate::interfaces::Numeric;
+use na::RealField;
+
+// Path parameter s in [0, 1] and its first two time derivatives
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScalingState<T: Numeric<T>> {
+    pub s: T,
+    pub ds: T,
+    pub dds: T,
+}
+
+// Rest-to-rest scaling of a path from s = 0 to s = 1, times outside [0, duration] are clamped
+pub trait TimeScaling<T: Numeric<T>> {
+    fn duration(&self) -> T;
+    fn sample(&self, t: T) -> ScalingState<T>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicTimeScaling<T: Numeric<T>> {
+    tf: T,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuinticTimeScaling<T: Numeric<T>> {
+    tf: T,
+}
+
+// Constant acceleration a up to cruise speed v, then constant deceleration
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrapezoidalTimeScaling<T: Numeric<T>> {
+    v: T,
+    a: T,
+}
+
+// Jerk limited profile, seven segments of jerk +j, 0, -j, 0, -j, 0, +j, built by from_limits
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SCurveTimeScaling<T: Numeric<T>> {
+    jerk: T,
+    // Duration of each jerk ramp, each constant acceleration phase and the cruise phase
+    t_jerk: T,
+    t_accel: T,
+    t_cruise: T,
+}
+
+fn clamp_time<T: Numeric<T> + RealField>(t: T, tf: T) -> T {
+    t.max(T::zero()).min(tf)
+}
+
+// Limits must be finite and positive, NaN fails both tests
+fn positive_finite<T: Numeric<T> + RealField>(limits: &[T]) -> bool {
+    limits.iter().all(|x| x.is_finite() && *x > T::zero())
+}
+
+impl<T: Numeric<T> + RealField> CubicTimeScaling<T> {
+    // Needs a positive, finite duration
+    pub fn new(tf: T) -> Option<Self> {
+        if !positive_finite(&[tf]) {
+            return None;
+        }
+        Some(CubicTimeScaling { tf })
+    }
+}
+
+impl<T: Numeric<T> + RealField> TimeScaling<T> for CubicTimeScaling<T> {
+    fn duration(&self) -> T {
+        self.tf
+    }
+
+    fn sample(&self, t: T) -> ScalingState<T> {
+        let tau = clamp_time(t, self.tf) / self.tf;
+        let (two, three, six): (T, T, T) = (na::convert(2.0), na::convert(3.0), na::convert(6.0));
+        ScalingState {
+            s: three * tau * tau - two * tau * tau * tau,
+            ds: six * tau * (T::one() - tau) / self.tf,
+            dds: six * (T::one() - two * tau) / (self.tf * self.tf),
+        }
+    }
+}
+
+impl<T: Numeric<T> + RealField> QuinticTimeScaling<T> {
+    // Needs a positive, finite duration
+    pub fn new(tf: T) -> Option<Self> {
+        if !positive_finite(&[tf]) {
+            return None;
+        }
+        Some(QuinticTimeScaling { tf })
+    }
+}
+
+impl<T: Numeric<T> + RealField> TimeScaling<T> for QuinticTimeScaling<T> {
+    fn duration(&self) -> T {
+        self.tf
+    }
+
+    fn sample(&self, t: T) -> ScalingState<T> {
+        let tau = clamp_time(t, self.tf) / self.tf;
+        let c = |x: f64| -> T { na::convert(x) };
+        let (tau2, tau3) = (tau * tau, tau * tau * tau);
+        ScalingState {
+            s: c(10.0) * tau3 - c(15.0) * tau3 * tau + c(6.0) * tau3 * tau2,
+            ds: (c(30.0) * tau2 - c(60.0) * tau3 + c(30.0) * tau2 * tau2) / self.tf,
+            dds: (c(60.0) * tau - c(180.0) * tau2 + c(120.0) * tau3) / (self.tf * self.tf),
+        }
+    }
+}
+
+impl<T: Numeric<T> + RealField> TrapezoidalTimeScaling<T> {
+    // Needs finite v, a > 0 and v^2 / a <= 1 so that the cruise phase is not negative
+    pub fn new(v: T, a: T) -> Option<Self> {
+        if !positive_finite(&[v, a]) || v * v / a > T::one() {
+            return None;
+        }
+        Some(TrapezoidalTimeScaling { v, a })
+    }
+
+    // Fastest profile within the limits, triangular when v_max is never reached
+    pub fn from_limits(v_max: T, a_max: T) -> Option<Self> {
+        if !positive_finite(&[v_max, a_max]) {
+            return None;
+        }
+        Some(TrapezoidalTimeScaling {
+            v: v_max.min(a_max.sqrt()),
+            a: a_max,
+        })
+    }
+
+    pub fn v(&self) -> T {
+        self.v
+    }
+
+    pub fn a(&self) -> T {
+        self.a
+    }
+}
+
+impl<T: Numeric<T> + RealField> TimeScaling<T> for TrapezoidalTimeScaling<T> {
+    fn duration(&self) -> T {
+        (self.a + self.v * self.v) / (self.v * self.a)
+    }
+
+    fn sample(&self, t: T) -> ScalingState<T> {
+        let tf = self.duration();
+        let t = clamp_time(t, tf);
+        let (v, a) = (self.v, self.a);
+        let ramp = v / a;
+        let half: T = na::convert(0.5);
+        if t <= ramp {
+            ScalingState {
+                s: half * a * t * t,
+                ds: a * t,
+                dds: a,
+            }
+        } else if t <= tf - ramp {
+            ScalingState {
+                s: v * t - half * v * v / a,
+                ds: v,
+                dds: T::zero(),
+            }
+        } else {
+            let rest = tf - t;
+            ScalingState {
+                s: T::one() - half * a * rest * rest,
+                ds: a * rest,
+                dds: -a,
+            }
+        }
+    }
+}
+
+impl<T: Numeric<T> + RealField> SCurveTimeScaling<T> {
+    // Fastest profile with |ds| <= v_max, |dds| <= a_max and |jerk| <= j_max
+    pub fn from_limits(v_max: T, a_max: T, j_max: T) -> Option<Self> {
+        if !positive_finite(&[v_max, a_max, j_max]) {
+            return None;
+        }
+        let (two, four): (T, T) = (na::convert(2.0), na::convert(4.0));
+        let a = a_max.min((v_max * j_max).sqrt());
+        let mut v = v_max;
+        // Accelerating to v and back to rest covers v (v / a + a / j)
+        if v * (v / a + a / j_max) > T::one() {
+            let r = a / j_max;
+            v = a * ((r * r + four / a).sqrt() - r) / two;
+            if v < a * a / j_max {
+                // Too short to ever hold the peak acceleration
+                v = (j_max / four).cbrt();
+            }
+        }
+        let peak = a.min((v * j_max).sqrt());
+        let t_jerk = peak / j_max;
+        let t_accel = (v / peak - t_jerk).max(T::zero());
+        let t_cruise = ((T::one() - v * (t_accel + two * t_jerk)) / v).max(T::zero());
+        Some(SCurveTimeScaling {
+            jerk: j_max,
+            t_jerk,
+            t_accel,
+            t_cruise,
+        })
+    }
+
+    pub fn jerk(&self) -> T {
+        self.jerk
+    }
+
+    pub fn t_jerk(&self) -> T {
+        self.t_jerk
+    }
+
+    pub fn t_accel(&self) -> T {
+        self.t_accel
+    }
+
+    pub fn t_cruise(&self) -> T {
+        self.t_cruise
+    }
+
+    pub fn peak_velocity(&self) -> T {
+        self.jerk * self.t_jerk * (self.t_jerk + self.t_accel)
+    }
+
+    pub fn peak_acceleration(&self) -> T {
+        self.jerk * self.t_jerk
+    }
+}
+
+impl<T: Numeric<T> + RealField> TimeScaling<T> for SCurveTimeScaling<T> {
+    fn duration(&self) -> T {
+        let (two, four): (T, T) = (na::convert(2.0), na::convert(4.0));
+        four * self.t_jerk + two * self.t_accel + self.t_cruise
+    }
+
+    fn sample(&self, t: T) -> ScalingState<T> {
+        let mut t = clamp_time(t, self.duration());
+        let (j, tj, ta) = (self.jerk, self.t_jerk, self.t_accel);
+        let segments = [
+            (j, tj),
+            (T::zero(), ta),
+            (-j, tj),
+            (T::zero(), self.t_cruise),
+            (-j, tj),
+            (T::zero(), ta),
+            (j, tj),
+        ];
+        let (half, sixth): (T, T) = (na::convert(0.5), na::convert(1.0 / 6.0));
+        let advance = |state: ScalingState<T>, jerk: T, dt: T| ScalingState {
+            s: state.s + state.ds * dt + half * state.dds * dt * dt + sixth * jerk * dt * dt * dt,
+            ds: state.ds + state.dds * dt + half * jerk * dt * dt,
+            dds: state.dds + jerk * dt,
+        };
+        let mut state = ScalingState {
+            s: T::zero(),
+            ds: T::zero(),
+            dds: T::zero(),
+        };
+        for (jerk, length) in segments {
+            if t <= length {
+                return advance(state, jerk, t);
+            }
+            state = advance(state, jerk, length);
+            t -= length;
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::lie_group_step;
+    use crate::groups::{Inverse, MatrixExp, MatrixLog, ToTwist, Twist};
+    use crate::helpers::near_zero;
+    use na::{DVector, Vector6};
+
+    fn profiles() -> Vec<Box<dyn TimeScaling<f64>>> {
+        vec![
+            Box::new(CubicTimeScaling::new(2.0).unwrap()),
+            Box::new(QuinticTimeScaling::new(2.0).unwrap()),
+            Box::new(TrapezoidalTimeScaling::new(0.8, 1.0).unwrap()),
+            Box::new(TrapezoidalTimeScaling::from_limits(5.0, 4.0).unwrap()),
+            Box::new(SCurveTimeScaling::from_limits(0.8, 1.5, 6.0).unwrap()),
+            Box::new(SCurveTimeScaling::from_limits(5.0, 4.0, 6.0).unwrap()),
+            Box::new(SCurveTimeScaling::from_limits(5.0, 40.0, 6.0).unwrap()),
+        ]
+    }
+
+    #[test]
+    fn time_scaling_textbook() {
+        assert!(near_zero(
+            CubicTimeScaling::new(2.0).unwrap().sample(0.6).s - 0.216,
+            Some(1e-12)
+        ));
+        assert!(near_zero(
+            QuinticTimeScaling::new(2.0).unwrap().sample(0.6).s - 0.16308,
+            Some(1e-12)
+        ));
+    }
+
+    #[test]
+    fn polynomial_invalid_duration() {
+        for tf in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(CubicTimeScaling::new(tf).is_none());
+            assert!(QuinticTimeScaling::new(tf).is_none());
+        }
+    }
+
+    #[test]
+    fn limited_invalid_limits() {
+        for bad in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(TrapezoidalTimeScaling::new(bad, 1.0).is_none());
+            assert!(TrapezoidalTimeScaling::new(0.5, bad).is_none());
+            assert!(TrapezoidalTimeScaling::from_limits(bad, 1.0).is_none());
+            assert!(TrapezoidalTimeScaling::from_limits(1.0, bad).is_none());
+            assert!(SCurveTimeScaling::from_limits(bad, 1.0, 1.0).is_none());
+            assert!(SCurveTimeScaling::from_limits(1.0, bad, 1.0).is_none());
+            assert!(SCurveTimeScaling::from_limits(1.0, 1.0, bad).is_none());
+        }
+    }
+
+    #[test]
+    fn time_scaling_rest_to_rest() {
+        for profile in profiles() {
+            let tf = profile.duration();
+            let (start, end) = (profile.sample(0.0), profile.sample(tf));
+            assert!(near_zero(start.s, Some(1e-12)) && near_zero(start.ds, Some(1e-12)));
+            assert!(near_zero(end.s - 1.0, Some(1e-9)) && near_zero(end.ds, Some(1e-9)));
+            // Clamped outside the interval
+            assert_eq!(profile.sample(-1.0), start);
+            assert_eq!(profile.sample(tf + 1.0).s, end.s);
+        }
+    }
+
+    #[test]
+    fn time_scaling_derivatives() {
+        let h = 1e-6;
+        for profile in profiles() {
+            let tf = profile.duration();
+            for k in 1..50 {
+                let t = tf * k as f64 / 50.0;
+                let (before, now, after) = (
+                    profile.sample(t - h),
+                    profile.sample(t),
+                    profile.sample(t + h),
+                );
+                assert!(near_zero(
+                    (after.s - before.s) / (2.0 * h) - now.ds,
+                    Some(1e-5)
+                ));
+                // The trapezoid's acceleration jumps at its corners
+                if (after.dds - before.dds).abs() < 1e-3 {
+                    let dds = (after.ds - before.ds) / (2.0 * h);
+                    assert!(near_zero(dds - now.dds, Some(1e-4)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quintic_zero_end_acceleration() {
+        let q = QuinticTimeScaling::new(3.0).unwrap();
+        assert!(near_zero(q.sample(0.0).dds, Some(1e-12)));
+        assert!(near_zero(q.sample(3.0).dds, Some(1e-12)));
+    }
+
+    #[test]
+    fn trapezoidal_limits() {
+        assert!(TrapezoidalTimeScaling::new(2.0, 1.0).is_none());
+        let trapezoid = TrapezoidalTimeScaling::new(0.8, 1.0).unwrap();
+        assert!(near_zero(
+            trapezoid.duration() - (1.0 + 0.64) / 0.8,
+            Some(1e-12)
+        ));
+        assert!(near_zero(trapezoid.sample(1.0).ds - 0.8, Some(1e-12)));
+        // Unreachable v_max gives a triangle with peak speed sqrt(a)
+        let triangle = TrapezoidalTimeScaling::from_limits(5.0, 4.0).unwrap();
+        assert!(near_zero(triangle.v() - 2.0, Some(1e-12)));
+        assert!(near_zero(triangle.duration() - 1.0, Some(1e-12)));
+    }
+
+    #[test]
+    fn s_curve_limits() {
+        let limits = [(0.8, 1.5, 6.0), (5.0, 4.0, 6.0), (5.0, 40.0, 6.0)];
+        for (v, a, j) in limits {
+            let curve = SCurveTimeScaling::from_limits(v, a, j).unwrap();
+            let tf = curve.duration();
+            let mut last = curve.sample(0.0);
+            for k in 1..=400 {
+                let now = curve.sample(tf * k as f64 / 400.0);
+                assert!(now.ds <= v + 1e-9 && now.dds.abs() <= a + 1e-9);
+                // Continuous acceleration, so jerk stays within its limit
+                assert!((now.dds - last.dds).abs() <= j * tf / 400.0 + 1e-9);
+                last = now;
+            }
+        }
+        // Long move reaches the velocity limit and cruises
+        let cruise = SCurveTimeScaling::from_limits(0.8, 1.5, 6.0).unwrap();
+        assert!(near_zero(cruise.peak_velocity() - 0.8, Some(1e-12)));
+        assert!(cruise.t_cruise() > 0.0);
+        // Very short move never holds the peak acceleration
+        let short = SCurveTimeScaling::from_limits(5.0, 40.0, 6.0).unwrap();
+        assert!(short.t_accel() == 0.0 && short.t_cruise() == 0.0);
+    }
+
+    #[test]
+    fn joint_space_straight_line() {
+        let start = DVector::from_vec(vec![0.2, -1.0, 0.5]);
+        let end = DVector::from_vec(vec![1.0, 0.5, -0.5]);
+        let delta = &end - &start;
+        let scaling = QuinticTimeScaling::new(2.0).unwrap();
+        // theta(t) = start + s(t) (end - start), so its velocity is ds(t) (end - start)
+        let path = |t: f64| {
+            let state = scaling.sample(t);
+            (&start + &delta * state.s, &delta * state.ds)
+        };
+        let (theta, dtheta) = path(0.0);
+        assert!(near_zero((theta - &start).norm(), Some(1e-12)));
+        assert!(near_zero(dtheta.norm(), Some(1e-12)));
+        let (theta, dtheta) = path(2.0);
+        assert!(near_zero((theta - &end).norm(), Some(1e-12)));
+        assert!(near_zero(dtheta.norm(), Some(1e-12)));
+        let (theta, dtheta) = path(1.0);
+        assert!(near_zero(
+            (theta - (&start + &end) * 0.5).norm(),
+            Some(1e-12)
+        ));
+        assert!(near_zero((dtheta - &delta * 0.9375).norm(), Some(1e-12)));
+    }
+
+    #[test]
+    fn se3_screw_path() {
+        let x_start = Vector6::new(0.1, 0.2, 0.3, 1.0, 0.0, 0.5).to_twist().exp();
+        let x_end = Vector6::new(-0.4, 0.3, 1.0, 0.5, 2.0, -1.0)
+            .to_twist()
+            .exp();
+        let twist = (x_start.inv() * x_end).log().to_twist();
+        let scaling = SCurveTimeScaling::from_limits(0.8, 1.5, 6.0).unwrap();
+        // X(t) = X_start exp([V] s(t)) has body twist V ds(t)
+        let sample = |t: f64| x_start * (twist.0 * scaling.sample(t).s).to_twist().exp();
+        let tf = scaling.duration();
+        assert!(near_zero((sample(0.0).0 - x_start.0).norm(), Some(1e-12)));
+        assert!(near_zero((sample(tf).0 - x_end.0).norm(), Some(1e-9)));
+        let steps = 2000;
+        let dt = tf / steps as f64;
+        let mut pose = x_start;
+        for k in 0..steps {
+            let ds = scaling.sample((k as f64 + 0.5) * dt).ds;
+            pose = lie_group_step(&pose, &Twist(twist.0 * ds), dt);
+            if k + 1 == steps / 2 {
+                assert!(near_zero((pose.0 - sample(tf / 2.0).0).norm(), Some(1e-4)));
+            }
+        }
+        assert!(near_zero((pose.0 - x_end.0).norm(), Some(1e-4)));
+    }
+}
@@ -4,48 +4,61 @@ use crate::helpers::{near_zero, near_zero_float};
 use crate::interfaces::{Numeric, NumericCompConvert};
 use na::{ComplexField, Vector3, Vector6};
 
-pub fn axis_ang_3<T: Numeric<T>>(exp: Vector3<T>) -> (Vector3<T>, T)
+pub fn axis_ang_3<T>(exp: Vector3<T>) -> Option<(Vector3<T>, T)>
 where
-    T: ComplexField<RealField = T>,
+    T: Numeric<T> + ComplexField<RealField = T>,
+    f64: From<T>,
 {
     let theta = exp.norm();
-    (exp / theta, theta)
+    if near_zero(theta, None) {
+        return None;
+    }
+    Some((exp / theta, theta))
 }
 
-pub fn axis_ang_3_nfloat<T: NumericCompConvert<T>>(exp: Vector3<T>) -> (Vector3<f64>, f64) {
+pub fn axis_ang_3_nfloat<T: NumericCompConvert<T>>(exp: Vector3<T>) -> Option<(Vector3<f64>, f64)> {
     let exp_c: Vector3<f64> = na::convert(exp);
     let theta = exp_c.norm();
-    (exp_c / theta, theta)
+    if near_zero_float(theta, None) {
+        return None;
+    }
+    Some((exp_c / theta, theta))
 }
 
-pub fn axis_ang_6_epsilon<T: Numeric<T> + PartialOrd>(exp: Vector6<T>, eps: T) -> (Vector6<T>, T)
+pub fn axis_ang_6_epsilon<T>(exp: Vector6<T>, eps: T) -> Option<(Vector6<T>, T)>
 where
-    T: ComplexField<RealField = T>,
+    T: Numeric<T> + ComplexField<RealField = T>,
     f64: From<T>,
 {
     let mut theta = exp.fixed_view::<3, 1>(0, 0).norm();
     if near_zero(theta, Some(eps)) {
         theta = exp.fixed_view::<3, 1>(3, 0).norm();
     }
-    (exp / theta, theta)
+    if near_zero(theta, None) {
+        return None;
+    }
+    Some((exp / theta, theta))
 }
 
-pub fn axis_ang_6<T: Numeric<T>>(exp: Vector6<T>) -> (Vector6<T>, T)
+pub fn axis_ang_6<T>(exp: Vector6<T>) -> Option<(Vector6<T>, T)>
 where
-    T: ComplexField<RealField = T>,
+    T: Numeric<T> + ComplexField<RealField = T>,
     f64: From<T>,
 {
     let mut theta = exp.fixed_view::<3, 1>(0, 0).norm();
     if near_zero(theta, None) {
         theta = exp.fixed_view::<3, 1>(3, 0).norm();
     }
-    (exp / theta, theta)
+    if near_zero(theta, None) {
+        return None;
+    }
+    Some((exp / theta, theta))
 }
 
-pub fn axis_ang_6_epsilon_nfloat<T: NumericCompConvert<T> + PartialOrd>(
+pub fn axis_ang_6_epsilon_nfloat<T: NumericCompConvert<T>>(
     exp: Vector6<T>,
     eps: T,
-) -> (Vector6<f64>, f64)
+) -> Option<(Vector6<f64>, f64)>
 where
     f64: From<T>,
 {
@@ -54,10 +67,13 @@ where
     if near_zero_float(theta, Some(f64::from(eps))) {
         theta = exp_c.fixed_view::<3, 1>(3, 0).norm();
     }
-    (exp_c / theta, theta)
+    if near_zero_float(theta, None) {
+        return None;
+    }
+    Some((exp_c / theta, theta))
 }
 
-pub fn axis_ang_6_nfloat<T: NumericCompConvert<T>>(exp: Vector6<T>) -> (Vector6<f64>, f64)
+pub fn axis_ang_6_nfloat<T: NumericCompConvert<T>>(exp: Vector6<T>) -> Option<(Vector6<f64>, f64)>
 where
     f64: From<T>,
 {
@@ -66,7 +82,10 @@ where
     if near_zero_float(theta, None) {
         theta = exp_c.fixed_view::<3, 1>(3, 0).norm();
     }
-    (exp_c / theta, theta)
+    if near_zero_float(theta, None) {
+        return None;
+    }
+    Some((exp_c / theta, theta))
 }
 
 #[cfg(test)]
@@ -76,7 +95,7 @@ mod tests {
     #[test]
     fn axis_ang_3_f64() {
         let exp = Vector3::new(1.0, 2.0, 3.0);
-        let (omg, theta) = axis_ang_3(exp);
+        let (omg, theta) = axis_ang_3(exp).unwrap();
         assert!(
             near_zero(
                 (omg - Vector3::new(0.2673, 0.5345, 0.8018)).norm(),
@@ -88,7 +107,7 @@ mod tests {
     #[test]
     fn axis_ang_3_i32() {
         let exp = Vector3::new(1, 2, 3);
-        let (omg, theta) = axis_ang_3_nfloat(exp);
+        let (omg, theta) = axis_ang_3_nfloat(exp).unwrap();
         assert!(
             near_zero(
                 (omg - Vector3::new(0.2673, 0.5345, 0.8018)).norm(),
@@ -100,7 +119,7 @@ mod tests {
     #[test]
     fn axis_ang_6_f64() {
         let exp = Vector6::new(1.0, 0.0, 0.0, 1.0, 2.0, 3.0);
-        let (s, theta) = axis_ang_6(exp);
+        let (s, theta) = axis_ang_6(exp).unwrap();
         assert!(
             near_zero(
                 (s - Vector6::new(1.0, 0.0, 0.0, 1.0, 2.0, 3.0)).norm(),
@@ -112,7 +131,7 @@ mod tests {
     #[test]
     fn axis_ang_6_i32() {
         let exp = Vector6::new(1, 0, 0, 1, 2, 3);
-        let (s, theta) = axis_ang_6_nfloat(exp);
+        let (s, theta) = axis_ang_6_nfloat(exp).unwrap();
         assert!(
             near_zero(
                 (s - Vector6::new(1.0, 0.0, 0.0, 1.0, 2.0, 3.0)).norm(),
@@ -124,7 +143,7 @@ mod tests {
     #[test]
     fn axis_ang_6_i32_eps() {
         let exp = Vector6::new(1, 0, 0, 2, 0, 0);
-        let (s, theta) = axis_ang_6_epsilon_nfloat(exp, 5);
+        let (s, theta) = axis_ang_6_epsilon_nfloat(exp, 5).unwrap();
         assert!(
             near_zero(
                 (s - Vector6::new(0.5, 0.0, 0.0, 1.0, 0.0, 0.0)).norm(),
@@ -136,7 +155,7 @@ mod tests {
     #[test]
     fn axis_ang_6_f64_eps() {
         let exp = Vector6::new(1.0, 0.0, 0.0, 2.0, 0.0, 0.0);
-        let (s, theta) = axis_ang_6_epsilon(exp, 5.0);
+        let (s, theta) = axis_ang_6_epsilon(exp, 5.0).unwrap();
         assert!(
             near_zero(
                 (s - Vector6::new(0.5, 0.0, 0.0, 1.0, 0.0, 0.0)).norm(),
@@ -144,4 +163,34 @@ mod tests {
             ) && near_zero(theta - 2.0, Some(1e-4))
         )
     }
+
+    #[test]
+    fn axis_ang_3_zero() {
+        assert_eq!(axis_ang_3(Vector3::new(0.0, 0.0, 0.0)), None);
+        assert_eq!(axis_ang_3_nfloat(Vector3::new(0, 0, 0)), None);
+    }
+
+    #[test]
+    fn axis_ang_6_zero() {
+        let exp = Vector6::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(axis_ang_6(exp), None);
+        assert_eq!(axis_ang_6_epsilon(exp, 1e-3), None);
+        assert_eq!(axis_ang_6_nfloat(Vector6::new(0, 0, 0, 0, 0, 0)), None);
+        assert_eq!(
+            axis_ang_6_epsilon_nfloat(Vector6::new(0, 0, 0, 0, 0, 0), 1),
+            None
+        );
+    }
+
+    #[test]
+    fn axis_ang_6_translation() {
+        let exp = Vector6::new(0.0, 0.0, 0.0, 0.0, 3.0, 4.0);
+        let (s, theta) = axis_ang_6(exp).unwrap();
+        assert!(
+            near_zero(
+                (s - Vector6::new(0.0, 0.0, 0.0, 0.0, 0.6, 0.8)).norm(),
+                Some(1e-9)
+            ) && near_zero(theta - 5.0, Some(1e-9))
+        )
+    }
 }
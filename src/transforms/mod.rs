@@ -1,3 +1,6 @@
 mod axis_ang;
 
-pub use axis_ang::axis_ang_3;
+pub use axis_ang::{
+    axis_ang_3, axis_ang_3_nfloat, axis_ang_6, axis_ang_6_epsilon, axis_ang_6_epsilon_nfloat,
+    axis_ang_6_nfloat,
+};
@@ -0,0 +1,551 @@
+extern crate nalgebra as na;
+use crate::dynamics::{DynamicChain, SpatialInertia};
+use crate::errors::UrdfError;
+use crate::groups::{EulerAngles, Se3Matrix, So3ToSe3, ToSo3, Twist};
+use crate::interfaces::Numeric;
+use crate::kinematics::{ScrewFrame, SerialChain};
+use na::{Dyn, Matrix3, OMatrix, OVector, RealField, Vector3, Vector6, U1, U6};
+use roxmltree::Node;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UrdfJointType {
+    Revolute,
+    Continuous,
+    Prismatic,
+    Fixed,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UrdfModel<T: Numeric<T>> {
+    pub joint_names: Vec<String>,
+    pub joint_types: Vec<UrdfJointType>,
+    // Pose of the tip link at zero joint values
+    pub home: Se3Matrix<T>,
+    // Space frame screw axes, one per movable joint
+    pub screws: Vec<Twist<T>>,
+    // (lower, upper), None for continuous joints
+    pub joint_limits: Vec<Option<(T, T)>>,
+    // Pose of each moving link frame at zero joint values
+    pub link_frames: Vec<Se3Matrix<T>>,
+    // Spatial inertia of each moving link in its link frame, fixed children lumped in
+    pub spatial_inertias: Vec<SpatialInertia<T>>,
+}
+
+struct Joint<T: Numeric<T>> {
+    name: String,
+    joint_type: UrdfJointType,
+    parent: String,
+    child: String,
+    origin: Se3Matrix<T>,
+    axis: Vector3<T>,
+    limit: Option<(T, T)>,
+}
+
+fn attribute<'a>(node: &Node<'a, '_>, name: &str) -> Result<&'a str, UrdfError> {
+    node.attribute(name)
+        .ok_or_else(|| UrdfError::MissingAttribute {
+            element: node.tag_name().name().to_string(),
+            attribute: name.to_string(),
+        })
+}
+
+fn child<'a, 'input>(node: &Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+// Whitespace separated finite numbers, "nan" and "inf" are rejected
+fn numbers<T: Numeric<T> + RealField>(s: &str) -> Result<Vec<T>, UrdfError> {
+    s.split_whitespace()
+        .map(|x| match x.parse::<f64>() {
+            Ok(x) if x.is_finite() => Ok(na::convert(x)),
+            _ => Err(UrdfError::InvalidNumber(s.to_string())),
+        })
+        .collect()
+}
+
+fn triple<T: Numeric<T> + RealField>(
+    node: &Node,
+    name: &str,
+    default: Vector3<T>,
+) -> Result<Vector3<T>, UrdfError> {
+    match node.attribute(name) {
+        None => Ok(default),
+        Some(s) => match numbers(s)?[..] {
+            [x, y, z] => Ok(Vector3::new(x, y, z)),
+            _ => Err(UrdfError::InvalidNumber(s.to_string())),
+        },
+    }
+}
+
+fn scalar<T: Numeric<T> + RealField>(node: &Node, name: &str) -> Result<T, UrdfError> {
+    let s = attribute(node, name)?;
+    match numbers(s)?[..] {
+        [x] => Ok(x),
+        _ => Err(UrdfError::InvalidNumber(s.to_string())),
+    }
+}
+
+// Default when the attribute is absent, an error when it is present but malformed
+fn optional_scalar<T: Numeric<T> + RealField>(
+    node: &Node,
+    name: &str,
+    default: T,
+) -> Result<T, UrdfError> {
+    match node.attribute(name) {
+        None => Ok(default),
+        Some(_) => scalar(node, name),
+    }
+}
+
+// <origin xyz rpy>, identity when absent
+fn origin<T: Numeric<T> + RealField>(node: &Node) -> Result<Se3Matrix<T>, UrdfError>
+where
+    f64: From<T>,
+{
+    let (xyz, rpy) = match child(node, "origin") {
+        Some(o) => (
+            triple(&o, "xyz", Vector3::zeros())?,
+            triple(&o, "rpy", Vector3::zeros())?,
+        ),
+        None => (Vector3::zeros(), Vector3::zeros()),
+    };
+    let rotation = EulerAngles::from_rpy(rpy[0], rpy[1], rpy[2]).to_so3();
+    Ok(rotation.to_se3(xyz))
+}
+
+fn link_inertia<T: Numeric<T> + RealField>(link: &Node) -> Result<SpatialInertia<T>, UrdfError>
+where
+    f64: From<T>,
+{
+    // A link without <inertial> is massless, otherwise <mass> and all of <inertia> are required
+    let inertial = match child(link, "inertial") {
+        Some(inertial) => inertial,
+        None => return Ok(SpatialInertia::zeros()),
+    };
+    let required = |tag: &str| {
+        child(&inertial, tag).ok_or_else(|| UrdfError::MissingAttribute {
+            element: "inertial".to_string(),
+            attribute: tag.to_string(),
+        })
+    };
+    let mass = scalar(&required("mass")?, "value")?;
+    let i = required("inertia")?;
+    let component = |name| scalar(&i, name);
+    let (ixx, ixy, ixz) = (component("ixx")?, component("ixy")?, component("ixz")?);
+    let (iyy, iyz, izz) = (component("iyy")?, component("iyz")?, component("izz")?);
+    let rotational = Matrix3::new(ixx, ixy, ixz, ixy, iyy, iyz, ixz, iyz, izz);
+    let g_c = SpatialInertia::from_mass_com(mass, &Vector3::zeros(), &rotational);
+    g_c.validate().map_err(|error| UrdfError::InvalidInertia {
+        link: attribute(link, "name").unwrap_or_default().to_string(),
+        error,
+    })?;
+    Ok(g_c.change_frame(&origin(&inertial)?))
+}
+
+fn parse_joint<T: Numeric<T> + RealField>(node: &Node) -> Result<Joint<T>, UrdfError>
+where
+    f64: From<T>,
+{
+    let joint_type = match attribute(node, "type")? {
+        "revolute" => UrdfJointType::Revolute,
+        "continuous" => UrdfJointType::Continuous,
+        "prismatic" => UrdfJointType::Prismatic,
+        "fixed" => UrdfJointType::Fixed,
+        other => return Err(UrdfError::UnsupportedJoint(other.to_string())),
+    };
+    let link_of = |tag: &str| -> Result<String, UrdfError> {
+        let element = child(node, tag).ok_or_else(|| UrdfError::MissingAttribute {
+            element: node.tag_name().name().to_string(),
+            attribute: tag.to_string(),
+        })?;
+        Ok(attribute(&element, "link")?.to_string())
+    };
+    let axis = match child(node, "axis") {
+        Some(a) => triple(&a, "xyz", Vector3::x())?.normalize(),
+        None => Vector3::x(),
+    };
+    // URDF requires <limit> on revolute and prismatic joints, its bounds default to 0
+    let limit = match (joint_type, child(node, "limit")) {
+        (UrdfJointType::Revolute | UrdfJointType::Prismatic, Some(l)) => Some((
+            optional_scalar(&l, "lower", T::zero())?,
+            optional_scalar(&l, "upper", T::zero())?,
+        )),
+        (UrdfJointType::Revolute | UrdfJointType::Prismatic, None) => {
+            return Err(UrdfError::MissingAttribute {
+                element: node.tag_name().name().to_string(),
+                attribute: "limit".to_string(),
+            })
+        }
+        _ => None,
+    };
+    Ok(Joint {
+        name: attribute(node, "name")?.to_string(),
+        joint_type,
+        parent: link_of("parent")?,
+        child: link_of("child")?,
+        origin: origin(node)?,
+        axis,
+        limit,
+    })
+}
+
+impl<T: Numeric<T> + RealField> UrdfModel<T>
+where
+    f64: From<T>,
+{
+    // Builds the serial chain from the root link to the tip link
+    pub fn parse(xml: &str, tip: &str) -> Result<Self, UrdfError> {
+        let doc = roxmltree::Document::parse(xml).map_err(|e| UrdfError::Xml(e.to_string()))?;
+        let robot = doc.root_element();
+        let mut inertias = HashMap::new();
+        for link in robot.children().filter(|n| n.has_tag_name("link")) {
+            inertias.insert(attribute(&link, "name")?.to_string(), link_inertia(&link)?);
+        }
+        let mut joints = Vec::new();
+        for joint in robot.children().filter(|n| n.has_tag_name("joint")) {
+            joints.push(parse_joint::<T>(&joint)?);
+        }
+        if !inertias.contains_key(tip) {
+            return Err(UrdfError::UnknownLink(tip.to_string()));
+        }
+
+        let mut path = Vec::new();
+        let mut link = tip;
+        while let Some(joint) = joints.iter().find(|j| j.child == link) {
+            path.push(joint);
+            link = &joint.parent;
+            if path.len() > joints.len() {
+                return Err(UrdfError::Xml("joints form a loop".to_string()));
+            }
+        }
+        path.reverse();
+
+        let mut model = UrdfModel {
+            joint_names: Vec::new(),
+            joint_types: Vec::new(),
+            home: Se3Matrix(na::Matrix4::identity()),
+            screws: Vec::new(),
+            joint_limits: Vec::new(),
+            link_frames: Vec::new(),
+            spatial_inertias: Vec::new(),
+        };
+        for joint in path {
+            model.home = model.home * joint.origin;
+            if joint.joint_type == UrdfJointType::Fixed {
+                continue;
+            }
+            let (r, p) = (model.home.to_so3(), model.home.0.fixed_view::<3, 1>(0, 3));
+            let omega = r.0 * joint.axis;
+            let screw = match joint.joint_type {
+                UrdfJointType::Prismatic => Vector6::new(
+                    T::zero(),
+                    T::zero(),
+                    T::zero(),
+                    omega[0],
+                    omega[1],
+                    omega[2],
+                ),
+                _ => {
+                    let v = -omega.cross(&p);
+                    Vector6::new(omega[0], omega[1], omega[2], v[0], v[1], v[2])
+                }
+            };
+            model.joint_names.push(joint.name.clone());
+            model.joint_types.push(joint.joint_type);
+            model.screws.push(Twist(screw));
+            model.joint_limits.push(joint.limit);
+            model.link_frames.push(model.home);
+            model
+                .spatial_inertias
+                .push(lumped_inertia(&joint.child, &joints, &inertias));
+        }
+        Ok(model)
+    }
+
+    pub fn from_file(path: &str, tip: &str) -> Result<Self, UrdfError> {
+        let xml = std::fs::read_to_string(path).map_err(|e| UrdfError::Io(e.to_string()))?;
+        UrdfModel::parse(&xml, tip)
+    }
+
+    pub fn dof(&self) -> usize {
+        self.screws.len()
+    }
+
+    pub fn to_serial_chain(&self) -> SerialChain<T, Dyn> {
+        SerialChain::from_twists(self.home, &self.screws, ScrewFrame::Space)
+    }
+
+    pub fn to_dynamic_chain(&self) -> DynamicChain<T, Dyn> {
+        let n = Dyn(self.dof());
+        DynamicChain::new(
+            OMatrix::from_fn_generic(U6, n, |r, c| self.screws[c].0[r]),
+            OVector::from_iterator_generic(n, U1, self.link_frames.iter().copied()),
+            self.home,
+            OVector::from_iterator_generic(n, U1, self.spatial_inertias.iter().copied()),
+        )
+    }
+}
+
+// Inertia of a link plus everything rigidly attached to it through fixed joints
+fn lumped_inertia<T: Numeric<T> + RealField>(
+    link: &str,
+    joints: &[Joint<T>],
+    inertias: &HashMap<String, SpatialInertia<T>>,
+) -> SpatialInertia<T>
+where
+    f64: From<T>,
+{
+    let own = inertias
+        .get(link)
+        .copied()
+        .unwrap_or_else(SpatialInertia::zeros);
+    joints
+        .iter()
+        .filter(|j| j.parent == link && j.joint_type == UrdfJointType::Fixed)
+        .fold(own, |g, j| {
+            g + lumped_inertia(&j.child, joints, inertias).change_frame(&j.origin)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::InertiaError;
+    use crate::groups::{ToSe3, Wrench};
+    use crate::helpers::near_zero;
+    use na::{DVector, Matrix4};
+    use std::f64::consts::FRAC_PI_2;
+
+    const ARM: &str = r#"
+        <robot name="arm">
+          <link name="base"/>
+          <link name="upper">
+            <inertial>
+              <origin xyz="0.5 0 0"/>
+              <mass value="2.0"/>
+              <inertia ixx="0.01" ixy="0" ixz="0" iyy="0.1" iyz="0" izz="0.1"/>
+            </inertial>
+          </link>
+          <link name="slider"/>
+          <link name="forearm">
+            <inertial>
+              <mass value="1.0"/>
+              <inertia ixx="0.01" ixy="0" ixz="0" iyy="0.01" iyz="0" izz="0.01"/>
+            </inertial>
+          </link>
+          <link name="tool">
+            <inertial>
+              <mass value="0.5"/>
+              <inertia ixx="0.001" ixy="0" ixz="0" iyy="0.001" iyz="0" izz="0.001"/>
+            </inertial>
+          </link>
+          <link name="camera"/>
+          <joint name="shoulder" type="revolute">
+            <parent link="base"/>
+            <child link="upper"/>
+            <origin xyz="0 0 0.5"/>
+            <axis xyz="0 0 1"/>
+            <limit lower="-1.5" upper="1.5" effort="10" velocity="1"/>
+          </joint>
+          <joint name="extend" type="prismatic">
+            <parent link="upper"/>
+            <child link="slider"/>
+            <origin xyz="1 0 0"/>
+            <axis xyz="1 0 0"/>
+            <limit lower="0" upper="0.3" effort="10" velocity="1"/>
+          </joint>
+          <joint name="elbow" type="continuous">
+            <parent link="slider"/>
+            <child link="forearm"/>
+            <origin xyz="0 0 0" rpy="1.5707963267948966 0 0"/>
+            <axis xyz="0 1 0"/>
+          </joint>
+          <joint name="flange" type="fixed">
+            <parent link="forearm"/>
+            <child link="tool"/>
+            <origin xyz="1 0 0"/>
+          </joint>
+          <joint name="mount" type="fixed">
+            <parent link="upper"/>
+            <child link="camera"/>
+            <origin xyz="0 0 0.1"/>
+          </joint>
+        </robot>"#;
+
+    #[test]
+    fn urdf_joints_and_limits() {
+        let model = UrdfModel::<f64>::parse(ARM, "tool").unwrap();
+        assert_eq!(model.dof(), 3);
+        assert_eq!(model.joint_names, vec!["shoulder", "extend", "elbow"]);
+        assert_eq!(
+            model.joint_types,
+            vec![
+                UrdfJointType::Revolute,
+                UrdfJointType::Prismatic,
+                UrdfJointType::Continuous
+            ]
+        );
+        assert_eq!(
+            model.joint_limits,
+            vec![Some((-1.5, 1.5)), Some((0.0, 0.3)), None]
+        );
+    }
+
+    #[test]
+    fn urdf_screws_and_home() {
+        let model = UrdfModel::<f64>::parse(ARM, "tool").unwrap();
+        let expected = [
+            Vector6::new(0.0, 0.0, 1.0, 0.0, 0.0, 0.0),
+            Vector6::new(0.0, 0.0, 0.0, 1.0, 0.0, 0.0),
+            // The rpy origin turns the elbow's local y axis into the space z axis
+            Vector6::new(0.0, 0.0, 1.0, 0.0, -1.0, 0.0),
+        ];
+        for (screw, e) in model.screws.iter().zip(expected) {
+            assert!(near_zero((screw.0 - e).norm(), Some(1e-9)));
+        }
+        let home = Matrix4::new(
+            1.0, 0.0, 0.0, 2.0, 0.0, 0.0, -1.0, 0.0, 0.0, 1.0, 0.0, 0.5, 0.0, 0.0, 0.0, 1.0,
+        );
+        assert!(near_zero((model.home.0 - home).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn urdf_chain_fkin() {
+        let model = UrdfModel::<f64>::parse(ARM, "tool").unwrap();
+        let chain = model.to_serial_chain();
+        let t = chain.fkin(&DVector::from_vec(vec![FRAC_PI_2, 0.2, -FRAC_PI_2]));
+        let expected = Matrix4::new(
+            1.0, 0.0, 0.0, 1.0, 0.0, 0.0, -1.0, 1.2, 0.0, 1.0, 0.0, 0.5, 0.0, 0.0, 0.0, 1.0,
+        )
+        .to_se3();
+        assert!(near_zero((t.0 - expected.0).norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn urdf_spatial_inertias() {
+        let model = UrdfModel::<f64>::parse(ARM, "tool").unwrap();
+        // Camera has no inertial block, so upper is its own mass offset along x
+        let g = model.spatial_inertias[0].0;
+        assert!(near_zero(g[(3, 3)] - 2.0, Some(1e-12)));
+        assert!(near_zero(g[(2, 2)] - (0.1 + 2.0 * 0.25), Some(1e-12)));
+        assert_eq!(model.spatial_inertias[1], SpatialInertia::zeros());
+        // The tool is lumped into the forearm
+        let g = model.spatial_inertias[2].0;
+        assert!(near_zero(g[(3, 3)] - 1.5, Some(1e-12)));
+        assert!(near_zero(g[(1, 1)] - (0.01 + 0.001 + 0.5), Some(1e-12)));
+    }
+
+    #[test]
+    fn urdf_dynamic_chain() {
+        let model = UrdfModel::<f64>::parse(ARM, "tool").unwrap();
+        let chain = model.to_dynamic_chain();
+        assert_eq!(chain.dof(), 3);
+        assert_eq!(chain.tip, model.home);
+        // Every joint turns about or slides across the vertical, so gravity loads none of them
+        let tau = chain.inverse_dynamics(
+            &DVector::zeros(3),
+            &DVector::zeros(3),
+            &DVector::zeros(3),
+            &Vector3::new(0.0, 0.0, -9.8),
+            &Wrench(Vector6::zeros()),
+        );
+        assert!(near_zero(tau.norm(), Some(1e-9)));
+    }
+
+    #[test]
+    fn urdf_errors() {
+        assert_eq!(
+            UrdfModel::<f64>::parse(ARM, "gripper"),
+            Err(UrdfError::UnknownLink("gripper".to_string()))
+        );
+        let floating = r#"<robot name="r"><link name="a"/><link name="b"/>
+            <joint name="j" type="floating"><parent link="a"/><child link="b"/></joint></robot>"#;
+        assert_eq!(
+            UrdfModel::<f64>::parse(floating, "b"),
+            Err(UrdfError::UnsupportedJoint("floating".to_string()))
+        );
+        assert!(matches!(
+            UrdfModel::<f64>::parse("<robot>", "b"),
+            Err(UrdfError::Xml(_))
+        ));
+    }
+
+    #[test]
+    fn urdf_malformed_numbers_and_limits() {
+        let bad_inertia = ARM.replace(r#"ixx="0.01" ixy="0""#, r#"ixx="heavy" ixy="0""#);
+        assert_eq!(
+            UrdfModel::<f64>::parse(&bad_inertia, "tool"),
+            Err(UrdfError::InvalidNumber("heavy".to_string()))
+        );
+        let bad_limit = ARM.replace(r#"lower="-1.5""#, r#"lower="-1.5rad""#);
+        assert_eq!(
+            UrdfModel::<f64>::parse(&bad_limit, "tool"),
+            Err(UrdfError::InvalidNumber("-1.5rad".to_string()))
+        );
+        let no_limit = ARM.replace(
+            r#"<limit lower="0" upper="0.3" effort="10" velocity="1"/>"#,
+            "",
+        );
+        assert_eq!(
+            UrdfModel::<f64>::parse(&no_limit, "tool"),
+            Err(UrdfError::MissingAttribute {
+                element: "joint".to_string(),
+                attribute: "limit".to_string()
+            })
+        );
+        let no_bounds = ARM.replace(r#"lower="0" upper="0.3" "#, "");
+        let model = UrdfModel::<f64>::parse(&no_bounds, "tool").unwrap();
+        assert_eq!(model.joint_limits[1], Some((0.0, 0.0)));
+    }
+
+    #[test]
+    fn urdf_invalid_inertials() {
+        let no_mass = ARM.replace(r#"<mass value="1.0"/>"#, "");
+        assert_eq!(
+            UrdfModel::<f64>::parse(&no_mass, "tool"),
+            Err(UrdfError::MissingAttribute {
+                element: "inertial".to_string(),
+                attribute: "mass".to_string()
+            })
+        );
+        let partial = ARM.replace(r#"ixx="0.001" ixy="0" "#, r#"ixx="0.001" "#);
+        assert_eq!(
+            UrdfModel::<f64>::parse(&partial, "tool"),
+            Err(UrdfError::MissingAttribute {
+                element: "inertia".to_string(),
+                attribute: "ixy".to_string()
+            })
+        );
+        let not_a_number = ARM.replace(r#"<mass value="2.0"/>"#, r#"<mass value="nan"/>"#);
+        assert_eq!(
+            UrdfModel::<f64>::parse(&not_a_number, "tool"),
+            Err(UrdfError::InvalidNumber("nan".to_string()))
+        );
+        let infinite = ARM.replace(r#"xyz="0 0 0.5""#, r#"xyz="0 0 inf""#);
+        assert_eq!(
+            UrdfModel::<f64>::parse(&infinite, "tool"),
+            Err(UrdfError::InvalidNumber("0 0 inf".to_string()))
+        );
+        // izz > ixx + iyy cannot come from a real mass distribution
+        let lopsided = ARM.replace(
+            r#"ixx="0.01" ixy="0" ixz="0" iyy="0.01" iyz="0" izz="0.01""#,
+            r#"ixx="0.01" ixy="0" ixz="0" iyy="0.01" iyz="0" izz="0.05""#,
+        );
+        assert_eq!(
+            UrdfModel::<f64>::parse(&lopsided, "tool"),
+            Err(UrdfError::InvalidInertia {
+                link: "forearm".to_string(),
+                error: InertiaError::TriangleInequality
+            })
+        );
+        let massless = ARM.replace(r#"<mass value="0.5"/>"#, r#"<mass value="0"/>"#);
+        assert_eq!(
+            UrdfModel::<f64>::parse(&massless, "tool"),
+            Err(UrdfError::InvalidInertia {
+                link: "tool".to_string(),
+                error: InertiaError::NonPositiveMass
+            })
+        );
+    }
+}